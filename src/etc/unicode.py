@@ -1,8 +1,8 @@
 #!/usr/bin/env python
 # xfail-license
 
-# This digests UnicodeData.txt and DerivedCoreProperties.txt and emits rust
-# code covering the core properties. Since this is a pretty rare event we
+# This digests UnicodeData.txt, DerivedCoreProperties.txt and SpecialCasing.txt
+# and emits rust code covering the core properties. Since this is a pretty rare event we
 # just store this out-of-line and check the unicode.rs file into git.
 #
 # The emitted code is "the minimum we think is necessary for libcore", that
@@ -29,6 +29,8 @@ def load_unicode_data(f):
     combines = []
     canon_decomp = {}
     compat_decomp = {}
+    to_upper = {}
+    to_lower = {}
     curr_cat = ""
     curr_combine = ""
     c_lo = 0
@@ -45,6 +47,11 @@ def load_unicode_data(f):
 
         code = int(code, 16)
 
+        if upcase != "":
+            to_upper[code] = [int(upcase, 16)]
+        if lowcsae != "":
+            to_lower[code] = [int(lowcsae, 16)]
+
         if decomp != "":
             if decomp.startswith('<'):
                 seq = []
@@ -87,7 +94,21 @@ def load_unicode_data(f):
             com_lo = code
             com_hi = code
 
-    return (canon_decomp, compat_decomp, gencats, combines)
+    return (canon_decomp, compat_decomp, gencats, combines, to_upper, to_lower)
+
+def load_special_casing(f, to_upper, to_lower):
+    # Multi-character mappings override the simple ones from UnicodeData.txt.
+    # Conditional (language or context sensitive) mappings are skipped.
+    fetch(f)
+    for line in fileinput.input(f):
+        line = line.split("#")[0]
+        fields = [x.strip() for x in line.split(";")]
+        if len(fields) != 5 or fields[4] != "":
+            continue
+        [code, lower, title, upper, cond] = fields
+        code = int(code, 16)
+        to_lower[code] = [int(c, 16) for c in lower.split()]
+        to_upper[code] = [int(c, 16) for c in upper.split()]
 
 
 def load_derived_core_properties(f):
@@ -346,13 +367,62 @@ def emit_decomp_module(f, canon, compat, combine):
     f.write("    }\n")
     f.write("}\n\n")
 
+def emit_conversions_module(f, to_upper, to_lower):
+    f.write("pub mod conversions {\n")
+    f.write("    use option::{Option, Some, None};\n")
+    f.write("    use vec::ImmutableVector;\n")
+    f.write("""
+    fn bsearch_case_table(c: char, r: &'static [(char, &'static [char])])
+                          -> Option<&'static [char]> {
+        use cmp::{Equal, Less, Greater};
+        match r.bsearch(|&(val, _)| {
+            if c == val { Equal }
+            else if val < c { Less }
+            else { Greater }
+        }) {
+            Some(idx) => {
+                let (_, result) = r[idx];
+                Some(result)
+            }
+            None => None
+        }
+    }
+
+    /// The characters `c` maps to when uppercased, if that is not just `c`.
+    pub fn to_upper(c: char) -> Option<&'static [char]> {
+        bsearch_case_table(c, to_upper_table)
+    }
+
+    /// The characters `c` maps to when lowercased, if that is not just `c`.
+    pub fn to_lower(c: char) -> Option<&'static [char]> {
+        bsearch_case_table(c, to_lower_table)
+    }\n\n""")
+
+    for (name, tbl) in [("to_upper_table", to_upper), ("to_lower_table", to_lower)]:
+        f.write("    static %s : &'static [(char, &'static [char])] = &[\n" % name)
+        keys = [k for k in tbl.keys() if tbl[k] != [k]]
+        keys.sort()
+        data = ""
+        first = True
+        for char in keys:
+            if not first:
+                data += ","
+            first = False
+            data += "(%s,&[%s])" % (escape_char(char),
+                                    ",".join([escape_char(d) for d in tbl[char]]))
+        format_table_content(f, data, 8)
+        f.write("\n    ];\n\n")
+    f.write("}\n\n")
+
 r = "unicode.rs"
 for i in [r]:
     if os.path.exists(i):
         os.remove(i);
 rf = open(r, "w")
 
-(canon_decomp, compat_decomp, gencats, combines) = load_unicode_data("UnicodeData.txt")
+(canon_decomp, compat_decomp, gencats, combines,
+ to_upper, to_lower) = load_unicode_data("UnicodeData.txt")
+load_special_casing("SpecialCasing.txt", to_upper, to_lower)
 
 # Preamble
 rf.write('''// Copyright 2012-2013 The Rust Project Developers. See the COPYRIGHT
@@ -378,3 +448,5 @@ emit_decomp_module(rf, canon_decomp, compat_decomp, combines)
 
 derived = load_derived_core_properties("DerivedCoreProperties.txt")
 emit_property_module(rf, "derived_property", derived)
+
+emit_conversions_module(rf, to_upper, to_lower)
@@ -77,10 +77,16 @@
 //! }
 //! ~~~
 
+use sort;
 use std::cmp::Eq;
+use std::from_str::{FromStr, from_str};
+use std::io::File;
+use std::io;
 use std::result::{Err, Ok};
 use std::result;
 use std::option::{Some, None};
+use std::str;
+use std::util;
 use std::vec;
 
 /// Name of an option. Either a string or a single char.
@@ -120,6 +126,20 @@ pub struct Opt {
     occur: Occur,
     /// Which options it aliases
     priv aliases: ~[Opt],
+    /// Whether a value of the form `@path` is replaced by the contents of
+    /// the file at `path`
+    file_backed: bool,
+    /// Whether this is a level option (see `optlevel`)
+    level: bool,
+    /// Whether the arguments after this option are all free (see
+    /// `optterminator`)
+    terminator: bool,
+    /// The most times a `Multi` option may occur, if limited
+    max_occurs: Option<uint>,
+    /// Names of the options it cannot be given together with
+    conflicts: ~[~str],
+    /// The character its values are split on by `opt_strs`, if any
+    separator: Option<char>,
 }
 
 /// Describes wether an option is given at all or has a value.
@@ -138,7 +158,11 @@ pub struct Matches {
     /// Values of the Options that matched
     priv vals: ~[~[Optval]],
     /// Free string fragments
-    free: ~[~str]
+    free: ~[~str],
+    /// Whether an option appeared after a free argument
+    priv permuted: bool,
+    /// The index in `free` at which the `--` ending the options was found
+    priv end_of_opts: Option<uint>,
 }
 
 /// The type returned when the command line does not conform to the
@@ -152,6 +176,13 @@ pub enum Fail_ {
     OptionMissing(~str),
     OptionDuplicated(~str),
     UnexpectedArgument(~str),
+    ArgumentFileUnreadable(~str, ~str),
+    ArgumentInvalid(~str, ~str),
+    OptionConflict(~str, ~str),
+    SubcommandMissing,
+    UnrecognizedSubcommand(~str),
+    AmbiguousSubcommand(~str, ~[~str]),
+    UnterminatedQuote(~str),
 }
 
 /// The type of failure that occured.
@@ -163,6 +194,13 @@ pub enum FailType {
     OptionMissing_,
     OptionDuplicated_,
     UnexpectedArgument_,
+    ArgumentFileUnreadable_,
+    ArgumentInvalid_,
+    OptionConflict_,
+    SubcommandMissing_,
+    UnrecognizedSubcommand_,
+    AmbiguousSubcommand_,
+    UnterminatedQuote_,
 }
 
 /// The result of parsing a command line with a set of options.
@@ -185,6 +223,52 @@ impl Name {
     }
 }
 
+impl Opt {
+    fn new(name: Name, hasarg: HasArg, occur: Occur) -> Opt {
+        Opt {
+            name: name,
+            hasarg: hasarg,
+            occur: occur,
+            aliases: ~[],
+            file_backed: false,
+            level: false,
+            terminator: false,
+            max_occurs: None,
+            conflicts: ~[],
+            separator: None,
+        }
+    }
+
+    /// Returns this option, declared to conflict with the options named in
+    /// `names`. Parsing fails if it is given together with any of them.
+    pub fn conflicts_with(self, names: &[&str]) -> Opt {
+        let mut opt = self;
+        opt.conflicts.extend(&mut names.iter().map(|&s| s.to_owned()));
+        opt
+    }
+
+    /// Returns this option, with each of its values holding a list
+    /// separated by `sep`, such as `a:b:c` for `:`. `opt_strs` returns the
+    /// elements of the lists rather than the values themselves.
+    ///
+    /// Empty elements are kept, so `a::b` gives `a`, an empty string and
+    /// `b`, and an empty value gives a single empty string.
+    pub fn separated_by(self, sep: char) -> Opt {
+        let mut opt = self;
+        opt.separator = Some(sep);
+        opt
+    }
+
+    /// Returns this option, with a value of the form `@path` replaced by
+    /// the contents of the file at `path`. Parsing fails if the file can't
+    /// be read.
+    pub fn file_backed(self) -> Opt {
+        let mut opt = self;
+        opt.file_backed = true;
+        opt
+    }
+}
+
 impl Matches {
     fn opt_vals(&self, nm: &str) -> ~[Optval] {
         match find_opt(self.opts, Name::from_str(nm)) {
@@ -212,6 +296,24 @@ impl Matches {
         self.opt_vals(nm).len()
     }
 
+    /// Returns the level of an option created with `optlevel`.
+    ///
+    /// If the option was given an explicit level, as in `--verbose=3`, the
+    /// last such level is returned and the other occurrences are ignored.
+    /// Otherwise the level is the number of times the option occurred, so
+    /// `-vvv` and `-v --verbose -v` are both level 3.
+    pub fn opt_level(&self, nm: &str) -> uint {
+        let mut count = 0;
+        let mut level = None;
+        for v in self.opt_vals(nm).iter() {
+            match *v {
+                Val(ref s) => level = from_str(*s),
+                Given => count += 1
+            }
+        }
+        level.unwrap_or(count)
+    }
+
     /// Returns true if any of several options were matched.
     pub fn opts_present(&self, names: &[~str]) -> bool {
         for nm in names.iter() {
@@ -238,18 +340,44 @@ impl Matches {
     /// option.
     ///
     /// Used when an option accepts multiple values.
+    ///
+    /// The values of an option declared with `separated_by` are split into
+    /// their elements.
     pub fn opt_strs(&self, nm: &str) -> ~[~str] {
+        let separator = match find_opt(self.opts, Name::from_str(nm)) {
+            Some(id) => self.opts[id].separator,
+            None => None
+        };
         let mut acc: ~[~str] = ~[];
         let r = self.opt_vals(nm);
         for v in r.iter() {
-            match *v {
-                Val(ref s) => acc.push((*s).clone()),
+            match (v, separator) {
+                (&Val(ref s), Some(sep)) => {
+                    acc.extend(&mut s.split(sep).map(|e| e.to_owned()))
+                }
+                (&Val(ref s), None) => acc.push((*s).clone()),
                 _ => ()
             }
         }
         acc
     }
 
+    /// Returns the arguments of the form `key=value` supplied to all
+    /// matches of the given option as pairs, in the order they were given,
+    /// e.g. `--set a=1 --set b=2` gives `[(a, 1), (b, 2)]`.
+    ///
+    /// Each argument is split at its first `=`, so the value may contain
+    /// more. An argument without any `=` gives the whole argument as the key
+    /// and an empty value. Keys which are given more than once are kept.
+    pub fn opt_map(&self, nm: &str) -> ~[(~str, ~str)] {
+        self.opt_strs(nm).move_iter().map(|s| {
+            match s.find('=') {
+                Some(i) => (s.slice_to(i).to_owned(), s.slice_from(i + 1).to_owned()),
+                None => (s, ~"")
+            }
+        }).collect()
+    }
+
     /// Returns the string argument supplied to a matching option or `None`.
     pub fn opt_str(&self, nm: &str) -> Option<~str> {
         let vals = self.opt_vals(nm);
@@ -277,12 +405,127 @@ impl Matches {
         }
     }
 
+    /// Returns the argument supplied to a matching option parsed as a `T`.
+    ///
+    /// Returns `None` if the option was not given a value, and an error
+    /// message naming the option and the value if the value does not parse.
+    pub fn opt_get<T: FromStr>(&self, nm: &str) -> Option<result::Result<T, ~str>> {
+        self.opt_str(nm).map(|s| {
+            match from_str(s.as_slice()) {
+                Some(v) => Ok(v),
+                None => Err(format!("Argument '{}' to option '{}' is invalid.",
+                                    s, nm))
+            }
+        })
+    }
+
+    /// Returns the argument supplied to a matching option as an `int`.
+    ///
+    /// Numbers are parsed the same way everywhere, independently of the
+    /// locale: an optional sign followed by ASCII digits. Digit group
+    /// separators such as the `,` in `1,000` are rejected.
+    pub fn opt_int(&self, nm: &str) -> Option<result::Result<int, ~str>> {
+        self.opt_get(nm)
+    }
+
+    /// Returns the argument supplied to a matching option as an `f64`.
+    ///
+    /// As with `opt_int` the parsing does not depend on the locale, and the
+    /// decimal separator is always `.`.
+    pub fn opt_float(&self, nm: &str) -> Option<result::Result<f64, ~str>> {
+        self.opt_get(nm)
+    }
+
+    /// Returns the free argument at position `idx` as an `i64`, parsed the
+    /// same way as by `opt_int`.
+    ///
+    /// Returns `None` if there are not that many free arguments, and an
+    /// error message naming the position if the argument does not parse.
+    pub fn free_int(&self, idx: uint) -> Option<result::Result<i64, ~str>> {
+        if idx >= self.free.len() {
+            return None;
+        }
+        let s = self.free[idx].as_slice();
+        Some(match from_str(s) {
+            Some(v) => Ok(v),
+            None => Err(format!("Free argument {} ('{}') is invalid.", idx, s))
+        })
+    }
+
+    /// Returns true if any option appeared after a free argument.
+    ///
+    /// Options and free arguments may be freely interleaved on the command
+    /// line; this reports whether the arguments relied on that, which tools
+    /// wanting a stricter "options first" style can warn about.
+    pub fn was_permuted(&self) -> bool {
+        self.permuted
+    }
+
+    /// Splits the free arguments into groups separated by `--`, for tools
+    /// which run several commands, e.g. `tool -- a b -- c d`.
+    ///
+    /// The first `--` ends the options and is not kept in `free`, but still
+    /// separates the arguments before it from those after it. Empty groups,
+    /// such as from a leading, trailing or doubled `--`, are dropped.
+    pub fn free_groups(&self) -> ~[~[~str]] {
+        let mut groups = ~[];
+        let mut group = ~[];
+        for (i, arg) in self.free.iter().enumerate() {
+            if (self.end_of_opts == Some(i) || *arg == ~"--") && !group.is_empty() {
+                groups.push(util::replace(&mut group, ~[]));
+            }
+            if *arg != ~"--" {
+                group.push(arg.clone());
+            }
+        }
+        if !group.is_empty() {
+            groups.push(group);
+        }
+        groups
+    }
+
+    /// Returns a copy of the free arguments sorted lexicographically.
+    ///
+    /// The sort is stable, and `free` keeps the original command line order.
+    pub fn free_sorted(&self) -> ~[~str] {
+        sort::merge_sort(self.free, |a, b| *a <= *b)
+    }
+
+    /// Fails if the same free argument appears more than once, naming the
+    /// first repeated one.
+    ///
+    /// Duplicates are allowed by default; tools that treat e.g. the same
+    /// input file given twice as a mistake can call this after parsing.
+    pub fn reject_duplicate_free(&self) -> result::Result<(), ~str> {
+        for (i, arg) in self.free.iter().enumerate() {
+            if self.free.slice_to(i).contains(arg) {
+                return Err(format!("Argument '{}' given more than once.", *arg));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Whether `opt` declares a conflict with `other`, under any of its names.
+fn conflicts_with(opt: &Opt, other: &Opt) -> bool {
+    opt.conflicts.iter().any(|nm| {
+        let nm = Name::from_str(nm.as_slice());
+        other.name == nm || other.aliases.iter().any(|a| a.name == nm)
+    })
 }
 
 fn is_arg(arg: &str) -> bool {
     arg.len() > 1 && arg[0] == '-' as u8
 }
 
+// A negative number such as `-5` is an argument rather than an option,
+// unless there is a short option named after its first digit.
+fn is_negative_number(opts: &[Opt], arg: &str) -> bool {
+    is_arg(arg) &&
+        arg.slice_from(1).chars().all(|c| c.is_ascii_digit()) &&
+        find_opt(opts, Short(arg.char_at(1))).is_none()
+}
+
 fn find_opt(opts: &[Opt], nm: Name) -> Option<uint> {
     // Search main options.
     let pos = opts.iter().position(|opt| opt.name == nm);
@@ -302,64 +545,70 @@ fn find_opt(opts: &[Opt], nm: Name) -> Option<uint> {
 
 /// Create an option that is required and takes an argument.
 pub fn reqopt(name: &str) -> Opt {
-    Opt {
-        name: Name::from_str(name),
-        hasarg: Yes,
-        occur: Req,
-        aliases: ~[]
-    }
+    Opt::new(Name::from_str(name), Yes, Req)
 }
 
 /// Create an option that is optional and takes an argument.
 pub fn optopt(name: &str) -> Opt {
-    Opt {
-        name: Name::from_str(name),
-        hasarg: Yes,
-        occur: Optional,
-        aliases: ~[]
-    }
+    Opt::new(Name::from_str(name), Yes, Optional)
 }
 
 /// Create an option that is optional and does not take an argument.
 pub fn optflag(name: &str) -> Opt {
-    Opt {
-        name: Name::from_str(name),
-        hasarg: No,
-        occur: Optional,
-        aliases: ~[]
-    }
+    Opt::new(Name::from_str(name), No, Optional)
 }
 
 /// Create an option that is optional, does not take an argument,
 /// and may occur multiple times.
 pub fn optflagmulti(name: &str) -> Opt {
-    Opt {
-        name: Name::from_str(name),
-        hasarg: No,
-        occur: Multi,
-        aliases: ~[]
-    }
+    Opt::new(Name::from_str(name), No, Multi)
 }
 
 /// Create an option that is optional and takes an optional argument.
+///
+/// Such an option can be used like a flag: `opt_present` is true whenever
+/// it is given, with or without a value, while `opt_str` is `None` unless a
+/// value was attached. `opt_default` maps the bare form to a default value,
+/// e.g. `--color` to `--color=auto`.
 pub fn optflagopt(name: &str) -> Opt {
-    Opt {
-        name: Name::from_str(name),
-        hasarg: Maybe,
-        occur: Optional,
-        aliases: ~[]
-    }
+    Opt::new(Name::from_str(name), Maybe, Optional)
 }
 
 /// Create an option that is optional, takes an argument, and may occur
 /// multiple times.
 pub fn optmulti(name: &str) -> Opt {
-    Opt {
-        name: Name::from_str(name),
-        hasarg: Yes,
-        occur: Multi,
-        aliases: ~[]
-    }
+    Opt::new(Name::from_str(name), Yes, Multi)
+}
+
+/// Create a level option, such as a verbosity level, which may occur
+/// multiple times.
+///
+/// Each occurrence raises the level by one. A long name can also set the
+/// level explicitly with `--name=N`; short names never take a value, so
+/// `-vv` is simply given twice. Use `Matches::opt_level` to get the result.
+pub fn optlevel(name: &str) -> Opt {
+    let mut opt = Opt::new(Name::from_str(name), Maybe, Multi);
+    opt.level = true;
+    opt
+}
+
+/// Create an option that is optional, takes an argument, and may occur at
+/// most `max` times. Occurring more often fails with `OptionDuplicated`.
+pub fn optmultimax(name: &str, max: uint) -> Opt {
+    let mut opt = optmulti(name);
+    opt.max_occurs = Some(max);
+    opt
+}
+
+/// Create an option that ends option parsing, like `--`.
+///
+/// Every argument after it is a free argument, even if it looks like an
+/// option. The terminator itself takes no value; `opt_present` reports
+/// whether it was given.
+pub fn optterminator(name: &str) -> Opt {
+    let mut opt = Opt::new(Name::from_str(name), No, Optional);
+    opt.terminator = true;
+    opt
 }
 
 impl Fail_ {
@@ -381,45 +630,278 @@ impl Fail_ {
             UnexpectedArgument(ref nm) => {
                 format!("Option '{}' does not take an argument.", *nm)
             }
+            ArgumentFileUnreadable(ref nm, ref path) => {
+                format!("Argument file '{}' for option '{}' could not be read.",
+                        *path, *nm)
+            }
+            ArgumentInvalid(ref nm, ref arg) => {
+                format!("Argument '{}' to option '{}' is invalid.", *arg, *nm)
+            }
+            OptionConflict(ref nm, ref other) => {
+                format!("Options '{}' and '{}' cannot be used together.", *nm, *other)
+            }
+            SubcommandMissing => ~"No command given.",
+            UnrecognizedSubcommand(ref nm) => {
+                format!("Unrecognized command: '{}'.", *nm)
+            }
+            AmbiguousSubcommand(ref nm, ref candidates) => {
+                format!("Command '{}' is ambiguous; it could be '{}'.",
+                        *nm, candidates.connect("', '"))
+            }
+            UnterminatedQuote(ref line) => {
+                format!("Unterminated quote in command line: {}", *line)
+            }
         }
     }
 }
 
+// Replaces a value of the form `@path` with the contents of the file at
+// `path`. Other values are returned unchanged.
+fn read_arg_file(nm: &Name, val: ~str) -> result::Result<~str, Fail_> {
+    if !val.starts_with("@") {
+        return Ok(val);
+    }
+    let path = Path::new(val.slice_from(1));
+    let mut contents = None;
+    io::io_error::cond.trap(|_| ()).inside(|| {
+        contents = File::open(&path).map(|mut f| f.read_to_end());
+    });
+    match contents.and_then(|bytes| str::from_utf8_owned_opt(bytes)) {
+        Some(s) => Ok(s),
+        None => Err(ArgumentFileUnreadable(nm.to_str(),
+                                           val.slice_from(1).to_owned()))
+    }
+}
+
 /// Parse command line arguments according to the provided options.
 ///
 /// On success returns `Ok(Opt)`. Use methods such as `opt_present`
 /// `opt_str`, etc. to interrogate results.  Returns `Err(Fail_)` on failure.
 /// Use `to_err_msg` to get an error message.
 pub fn getopts(args: &[~str], opts: &[Opt]) -> Result {
+    let (matches, mut errors) = parse(args, opts);
+    if errors.is_empty() {
+        Ok(matches)
+    } else {
+        Err(errors.shift())
+    }
+}
+
+/// Parse command line arguments like `getopts`, but report every problem
+/// found instead of only the first one.
+pub fn validate_all(args: &[~str], opts: &[Opt])
+                    -> result::Result<Matches, ~[Fail_]> {
+    let (matches, errors) = parse(args, opts);
+    if errors.is_empty() {
+        Ok(matches)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Parse a command line of the form `[options] command [args...]`.
+///
+/// The options before the command are parsed according to `opts`, and the
+/// command is looked up in `commands`. A unique prefix of a command selects
+/// it, so `b` may stand for `build`, but an exact match always wins. Returns
+/// the options, the full name of the command and the arguments following
+/// it, which are left for the command to parse.
+///
+/// An option with an optional argument never takes the following word as
+/// its value here, since that word could be the command.
+pub fn getopts_subcommand(args: &[~str], opts: &[Opt], commands: &[&str])
+                          -> result::Result<(Matches, ~str, ~[~str]), Fail_> {
+    // `end` is where the options stop, and `i` where the command is.
+    let mut end = args.len();
+    let mut i = 0;
+    while i < args.len() {
+        let cur = args[i].as_slice();
+        if cur == "--" {
+            end = i;
+            i += 1;
+            break;
+        }
+        if !is_arg(cur) || is_negative_number(opts, cur) {
+            end = i;
+            break;
+        }
+        if takes_next_arg(opts, cur) {
+            i += 1;
+        }
+        i += 1;
+    }
+    let matches = match getopts(args.slice_to(end), opts) {
+        Ok(m) => m,
+        Err(f) => return Err(f)
+    };
+    if i >= args.len() {
+        return Err(SubcommandMissing);
+    }
+    match resolve_subcommand(args[i].as_slice(), commands) {
+        Ok(name) => Ok((matches, name, args.slice_from(i + 1).to_owned())),
+        Err(f) => Err(f)
+    }
+}
+
+/// Parse a command line given as a single string, such as `-o out "a b"`.
+///
+/// The string is split into arguments at whitespace, except inside double
+/// quotes, which are removed. A backslash makes the character after it
+/// literal, inside quotes or not, so `\"` is a quote and `\ ` a space that
+/// does not split. `""` is an empty argument. A double quote that is never
+/// closed fails with `UnterminatedQuote`.
+pub fn getopts_from_str(line: &str, opts: &[Opt]) -> Result {
+    match split_command_line(line) {
+        Some(args) => getopts(args, opts),
+        None => Err(UnterminatedQuote(line.to_owned()))
+    }
+}
+
+// Splits `line` into arguments as described for `getopts_from_str`, or
+// returns `None` if a quote is left open.
+fn split_command_line(line: &str) -> Option<~[~str]> {
+    let mut args = ~[];
+    let mut cur = ~"";
+    // Whether `cur` is an argument, which it may be while still empty.
+    let mut in_arg = false;
+    let mut in_quotes = false;
+    let mut escaped = false;
+    for ch in line.chars() {
+        if escaped {
+            cur.push_char(ch);
+            escaped = false;
+        } else if ch == '\\' {
+            in_arg = true;
+            escaped = true;
+        } else if ch == '"' {
+            in_arg = true;
+            in_quotes = !in_quotes;
+        } else if ch.is_whitespace() && !in_quotes {
+            if in_arg {
+                args.push(util::replace(&mut cur, ~""));
+                in_arg = false;
+            }
+        } else {
+            cur.push_char(ch);
+            in_arg = true;
+        }
+    }
+    if in_quotes {
+        return None;
+    }
+    if escaped {
+        // A trailing backslash stands for itself.
+        cur.push_char('\\');
+    }
+    if in_arg {
+        args.push(cur);
+    }
+    Some(args)
+}
+
+/// Return the spellings of the options in `opts` that start with `partial`,
+/// for shell completion.
+///
+/// Short options are spelled `-x` and long options `--name`, and aliases
+/// are included. The result is sorted and free of duplicates, so for
+/// example `--ver` may give `["--verbose", "--version"]`.
+pub fn complete(opts: &[Opt], partial: &str) -> ~[~str] {
+    fn collect(opts: &[Opt], partial: &str, out: &mut ~[~str]) {
+        for opt in opts.iter() {
+            let spelling = match opt.name {
+                Short(ch) => format!("-{}", ch),
+                Long(ref s) => format!("--{}", *s)
+            };
+            if spelling.starts_with(partial) && !out.contains(&spelling) {
+                out.push(spelling);
+            }
+            collect(opt.aliases, partial, out);
+        }
+    }
+
+    let mut candidates = ~[];
+    collect(opts, partial, &mut candidates);
+    sort::merge_sort(candidates, |a, b| *a <= *b)
+}
+
+// Whether the option argument `arg` will take the argument after it as its
+// value.
+fn takes_next_arg(opts: &[Opt], arg: &str) -> bool {
+    let takes = |nm: Name| match find_opt(opts, nm) {
+        Some(id) => opts[id].hasarg == Yes && !opts[id].level,
+        None => false
+    };
+    if arg.starts_with("--") {
+        !arg.contains_char('=') && takes(Long(arg.slice_from(2).to_owned()))
+    } else {
+        // The first short option with an argument takes the rest of the
+        // word, if there is any.
+        for (j, ch) in arg.slice_from(1).char_indices() {
+            if takes(Short(ch)) {
+                return j + ch.len_utf8_bytes() == arg.len() - 1;
+            }
+        }
+        false
+    }
+}
+
+// Looks up `name` in `commands`, allowing unique prefixes.
+fn resolve_subcommand(name: &str, commands: &[&str]) -> result::Result<~str, Fail_> {
+    if commands.contains(&name) {
+        return Ok(name.to_owned());
+    }
+    let candidates: ~[~str] = commands.iter()
+                                      .filter(|c| c.starts_with(name))
+                                      .map(|&c| c.to_owned())
+                                      .collect();
+    match candidates.len() {
+        0 => Err(UnrecognizedSubcommand(name.to_owned())),
+        1 => Ok(candidates[0].clone()),
+        _ => Err(AmbiguousSubcommand(name.to_owned(), candidates))
+    }
+}
+
+// Parses the whole command line, skipping over the arguments that are in
+// error. The failures are returned in the order in which they were found.
+fn parse(args: &[~str], opts: &[Opt]) -> (Matches, ~[Fail_]) {
     let n_opts = opts.len();
 
     fn f(_x: uint) -> ~[Optval] { return ~[]; }
 
     let mut vals = vec::from_fn(n_opts, f);
     let mut free: ~[~str] = ~[];
+    let mut permuted = false;
+    let mut end_of_opts = None;
+    let mut errors: ~[Fail_] = ~[];
     let l = args.len();
     let mut i = 0;
     while i < l {
         let cur = args[i].clone();
         let curlen = cur.len();
-        if !is_arg(cur) {
+        if !is_arg(cur) || is_negative_number(opts, cur) {
             free.push(cur);
         } else if cur == ~"--" {
+            // Only the first `--` ends the options; any later `--` is copied
+            // into `free` along with the rest.
+            end_of_opts = Some(free.len());
             let mut j = i + 1;
             while j < l { free.push(args[j].clone()); j += 1; }
             break;
         } else {
+            if !free.is_empty() {
+                permuted = true;
+            }
             let mut names;
             let mut i_arg = None;
             if cur[1] == '-' as u8 {
                 let tail = cur.slice(2, curlen);
-                let tail_eq: ~[&str] = tail.split('=').collect();
-                if tail_eq.len() <= 1 {
-                    names = ~[Long(tail.to_owned())];
-                } else {
-                    names =
-                        ~[Long(tail_eq[0].to_owned())];
-                    i_arg = Some(tail_eq[1].to_owned());
+                // Only the first `=` ends the name; the value may hold more.
+                match tail.find('=') {
+                    None => names = ~[Long(tail.to_owned())],
+                    Some(eq) => {
+                        names = ~[Long(tail.slice_to(eq).to_owned())];
+                        i_arg = Some(tail.slice_from(eq + 1).to_owned());
+                    }
                 }
             } else {
                 let mut j = 1;
@@ -441,6 +923,7 @@ pub fn getopts(args: &[~str], opts: &[Opt]) -> Result {
                       None => {
                         let arg_follows =
                             last_valid_opt_id.is_some() &&
+                            !opts[last_valid_opt_id.unwrap()].level &&
                             match opts[last_valid_opt_id.unwrap()]
                               .hasarg {
 
@@ -460,36 +943,74 @@ pub fn getopts(args: &[~str], opts: &[Opt]) -> Result {
                 }
             }
             let mut name_pos = 0;
+            let mut terminated = false;
             for nm in names.iter() {
                 name_pos += 1;
                 let optid = match find_opt(opts, (*nm).clone()) {
                   Some(id) => id,
-                  None => return Err(UnrecognizedOption(nm.to_str()))
+                  None => {
+                    errors.push(UnrecognizedOption(nm.to_str()));
+                    continue;
+                  }
                 };
-                match opts[optid].hasarg {
+                let value = match opts[optid].hasarg {
+                  _ if opts[optid].level => {
+                    // Only `--name=N` sets a level, and it must be a number.
+                    match i_arg {
+                      Some(ref arg) if from_str::<uint>(*arg).is_none() => {
+                        errors.push(ArgumentInvalid(nm.to_str(), arg.clone()));
+                        continue;
+                      }
+                      _ => i_arg.clone()
+                    }
+                  }
                   No => {
                     if !i_arg.is_none() {
-                        return Err(UnexpectedArgument(nm.to_str()));
+                        errors.push(UnexpectedArgument(nm.to_str()));
+                        continue;
                     }
-                    vals[optid].push(Given);
+                    None
                   }
                   Maybe => {
                     if !i_arg.is_none() {
-                        vals[optid].push(Val((i_arg.clone()).unwrap()));
-                    } else if name_pos < names.len() ||
-                                  i + 1 == l || is_arg(args[i + 1]) {
-                        vals[optid].push(Given);
-                    } else { i += 1; vals[optid].push(Val(args[i].clone())); }
+                        Some((i_arg.clone()).unwrap())
+                    } else if name_pos < names.len() || i + 1 == l ||
+                                  (is_arg(args[i + 1]) &&
+                                   !is_negative_number(opts, args[i + 1])) {
+                        None
+                    } else { i += 1; Some(args[i].clone()) }
                   }
                   Yes => {
                     if !i_arg.is_none() {
-                        vals[optid].push(Val(i_arg.clone().unwrap()));
+                        Some(i_arg.clone().unwrap())
                     } else if i + 1 == l {
-                        return Err(ArgumentMissing(nm.to_str()));
-                    } else { i += 1; vals[optid].push(Val(args[i].clone())); }
+                        errors.push(ArgumentMissing(nm.to_str()));
+                        continue;
+                    } else { i += 1; Some(args[i].clone()) }
                   }
+                };
+                match value {
+                    None => vals[optid].push(Given),
+                    Some(v) => {
+                        let v = if opts[optid].file_backed {
+                            match read_arg_file(nm, v) {
+                                Ok(v) => v,
+                                Err(f) => { errors.push(f); continue; }
+                            }
+                        } else {
+                            v
+                        };
+                        vals[optid].push(Val(v));
+                    }
+                }
+                if opts[optid].terminator {
+                    terminated = true;
                 }
             }
+            if terminated {
+                free.push_all(args.slice_from(i + 1));
+                break;
+            }
         }
         i += 1;
     }
@@ -499,21 +1020,38 @@ pub fn getopts(args: &[~str], opts: &[Opt]) -> Result {
         let occ = opts[i].occur;
         if occ == Req {
             if n == 0 {
-                return Err(OptionMissing(opts[i].name.to_str()));
+                errors.push(OptionMissing(opts[i].name.to_str()));
             }
         }
-        if occ != Multi {
-            if n > 1 {
-                return Err(OptionDuplicated(opts[i].name.to_str()));
+        let too_many = match opts[i].max_occurs {
+            Some(max) => n > max,
+            None => false
+        };
+        if (occ != Multi && n > 1) || too_many {
+            errors.push(OptionDuplicated(opts[i].name.to_str()));
+        }
+        if n > 0 {
+            for other in opts[i].conflicts.iter() {
+                match find_opt(opts, Name::from_str(other.as_slice())) {
+                    // A conflict declared on both options is reported once.
+                    Some(j) if !vals[j].is_empty() &&
+                               !(j < i && conflicts_with(&opts[j], &opts[i])) => {
+                        errors.push(OptionConflict(opts[i].name.to_str(),
+                                                   opts[j].name.to_str()));
+                    }
+                    _ => {}
+                }
             }
         }
         i += 1;
     }
-    Ok(Matches {
+    (Matches {
         opts: opts.to_owned(),
         vals: vals,
-        free: free
-    })
+        free: free,
+        permuted: permuted,
+        end_of_opts: end_of_opts
+    }, errors)
 }
 
 /// A module which provides a way to specify descriptions and
@@ -522,6 +1060,11 @@ pub mod groups {
     use getopts::{HasArg, Long, Maybe, Multi, No, Occur, Opt, Optional, Req};
     use getopts::{Short, Yes};
 
+    use std::io::Writer;
+    use std::io::Decorator;
+    use std::io::mem::MemWriter;
+    use std::str;
+
     /// One group of options, e.g., both -h and --help, along with
     /// their shared description and properties.
     #[deriving(Clone, Eq)]
@@ -537,7 +1080,19 @@ pub mod groups {
         /// Whether it has an argument
         hasarg: HasArg,
         /// How often it can occur
-        occur: Occur
+        occur: Occur,
+        /// Whether it is a level option
+        level: bool,
+        /// Whether it ends option parsing
+        terminator: bool,
+        /// The most times it may occur, if limited
+        max_occurs: Option<uint>,
+        /// Names of the options it cannot be given together with
+        conflicts: ~[~str],
+        /// The character its values are split on, if any
+        separator: Option<char>,
+        /// Whether an `@path` value is replaced by the file's contents
+        file_backed: bool,
     }
 
     impl OptGroup {
@@ -549,38 +1104,59 @@ pub mod groups {
                 long_name: long_name,
                 hasarg: hasarg,
                 occur: occur,
+                level: level,
+                terminator: terminator,
+                max_occurs: max_occurs,
+                conflicts: conflicts,
+                separator: separator,
+                file_backed: file_backed,
                 _
             } = (*self).clone();
 
-            match (short_name.len(), long_name.len()) {
+            let mut opt = match (short_name.len(), long_name.len()) {
                 (0,0) => fail!("this long-format option was given no name"),
-                (0,_) => Opt {
-                    name: Long((long_name)),
-                    hasarg: hasarg,
-                    occur: occur,
-                    aliases: ~[]
-                },
-                (1,0) => Opt {
-                    name: Short(short_name.char_at(0)),
-                    hasarg: hasarg,
-                    occur: occur,
-                    aliases: ~[]
-                },
-                (1,_) => Opt {
-                    name: Long((long_name)),
-                    hasarg: hasarg,
-                    occur:  occur,
-                    aliases: ~[
-                        Opt {
-                            name: Short(short_name.char_at(0)),
-                            hasarg: hasarg,
-                            occur:  occur,
-                            aliases: ~[]
-                        }
-                    ]
+                (0,_) => Opt::new(Long((long_name)), hasarg, occur),
+                (1,0) => Opt::new(Short(short_name.char_at(0)), hasarg, occur),
+                (1,_) => {
+                    let mut opt = Opt::new(Long((long_name)), hasarg, occur);
+                    opt.aliases = ~[
+                        Opt::new(Short(short_name.char_at(0)), hasarg, occur)
+                    ];
+                    opt
                 },
                 (_,_) => fail!("something is wrong with the long-form opt")
-            }
+            };
+            opt.level = level;
+            opt.terminator = terminator;
+            opt.max_occurs = max_occurs;
+            opt.conflicts = conflicts;
+            opt.separator = separator;
+            opt.file_backed = file_backed;
+            opt
+        }
+
+        /// Returns this group, declared to conflict with the options named
+        /// in `names`. See `Opt::conflicts_with`.
+        pub fn conflicts_with(self, names: &[&str]) -> OptGroup {
+            let mut opt = self;
+            opt.conflicts.extend(&mut names.iter().map(|&s| s.to_owned()));
+            opt
+        }
+
+        /// Returns this group, with its values split on `sep`. See
+        /// `Opt::separated_by`.
+        pub fn separated_by(self, sep: char) -> OptGroup {
+            let mut opt = self;
+            opt.separator = Some(sep);
+            opt
+        }
+
+        /// Returns this group, with `@path` values read from files. See
+        /// `Opt::file_backed`.
+        pub fn file_backed(self) -> OptGroup {
+            let mut opt = self;
+            opt.file_backed = true;
+            opt
         }
     }
 
@@ -594,7 +1170,13 @@ pub mod groups {
             hint: hint.to_owned(),
             desc: desc.to_owned(),
             hasarg: Yes,
-            occur: Req
+            occur: Req,
+            level: false,
+            terminator: false,
+            max_occurs: None,
+            conflicts: ~[],
+            separator: None,
+            file_backed: false
         }
     }
 
@@ -608,7 +1190,13 @@ pub mod groups {
             hint: hint.to_owned(),
             desc: desc.to_owned(),
             hasarg: Yes,
-            occur: Optional
+            occur: Optional,
+            level: false,
+            terminator: false,
+            max_occurs: None,
+            conflicts: ~[],
+            separator: None,
+            file_backed: false
         }
     }
 
@@ -622,7 +1210,13 @@ pub mod groups {
             hint: ~"",
             desc: desc.to_owned(),
             hasarg: No,
-            occur: Optional
+            occur: Optional,
+            level: false,
+            terminator: false,
+            max_occurs: None,
+            conflicts: ~[],
+            separator: None,
+            file_backed: false
         }
     }
 
@@ -637,11 +1231,18 @@ pub mod groups {
             hint: ~"",
             desc: desc.to_owned(),
             hasarg: No,
-            occur: Multi
+            occur: Multi,
+            level: false,
+            terminator: false,
+            max_occurs: None,
+            conflicts: ~[],
+            separator: None,
+            file_backed: false
         }
     }
 
     /// Create a long option that is optional and takes an optional argument.
+    /// See `getopts::optflagopt` for how its presence and value are reported.
     pub fn optflagopt(short_name: &str, long_name: &str, desc: &str, hint: &str) -> OptGroup {
         let len = short_name.len();
         assert!(len == 1 || len == 0);
@@ -651,7 +1252,35 @@ pub mod groups {
             hint: hint.to_owned(),
             desc: desc.to_owned(),
             hasarg: Maybe,
-            occur: Optional
+            occur: Optional,
+            level: false,
+            terminator: false,
+            max_occurs: None,
+            conflicts: ~[],
+            separator: None,
+            file_backed: false
+        }
+    }
+
+    /// Create a level option, which may occur multiple times and does not
+    /// take an argument except in the long form `--name=N`. See
+    /// `getopts::optlevel`.
+    pub fn optlevel(short_name: &str, long_name: &str, desc: &str, hint: &str) -> OptGroup {
+        let len = short_name.len();
+        assert!(len == 1 || len == 0);
+        OptGroup {
+            short_name: short_name.to_owned(),
+            long_name: long_name.to_owned(),
+            hint: hint.to_owned(),
+            desc: desc.to_owned(),
+            hasarg: Maybe,
+            occur: Multi,
+            level: true,
+            terminator: false,
+            max_occurs: None,
+            conflicts: ~[],
+            separator: None,
+            file_backed: false
         }
     }
 
@@ -666,7 +1295,43 @@ pub mod groups {
             hint: hint.to_owned(),
             desc: desc.to_owned(),
             hasarg: Yes,
-            occur: Multi
+            occur: Multi,
+            level: false,
+            terminator: false,
+            max_occurs: None,
+            conflicts: ~[],
+            separator: None,
+            file_backed: false
+        }
+    }
+
+    /// Create a long option that takes an argument and may occur at most
+    /// `max` times. See `getopts::optmultimax`.
+    pub fn optmultimax(short_name: &str, long_name: &str, desc: &str, hint: &str,
+                       max: uint) -> OptGroup {
+        let mut opt = optmulti(short_name, long_name, desc, hint);
+        opt.max_occurs = Some(max);
+        opt
+    }
+
+    /// Create a long option that ends option parsing like `--`, such as
+    /// `--args` in a wrapper tool. See `getopts::optterminator`.
+    pub fn optterminator(short_name: &str, long_name: &str, desc: &str) -> OptGroup {
+        let len = short_name.len();
+        assert!(len == 1 || len == 0);
+        OptGroup {
+            short_name: short_name.to_owned(),
+            long_name: long_name.to_owned(),
+            hint: ~"",
+            desc: desc.to_owned(),
+            hasarg: No,
+            occur: Optional,
+            level: false,
+            terminator: true,
+            max_occurs: None,
+            conflicts: ~[],
+            separator: None,
+            file_backed: false
         }
     }
 
@@ -675,86 +1340,140 @@ pub mod groups {
         ::getopts::getopts(args, opts.map(|x| x.long_to_short()))
     }
 
-    /// Derive a usage message from a set of long options.
+    // The width `usage` wraps descriptions to, and the column they start in.
+    static USAGE_WIDTH: uint = 80;
+    static DESC_COLUMN: uint = 24;
+
+    /// Derive a usage message from a set of long options, wrapped to 80
+    /// columns.
     pub fn usage(brief: &str, opts: &[OptGroup]) -> ~str {
+        usage_with_width(brief, opts, USAGE_WIDTH)
+    }
 
-        let desc_sep = "\n" + " ".repeat(24);
-
-        let mut rows = opts.iter().map(|optref| {
-            let OptGroup{short_name: short_name,
-                         long_name: long_name,
-                         hint: hint,
-                         desc: desc,
-                         hasarg: hasarg,
-                         _} = (*optref).clone();
-
-            let mut row = " ".repeat(4);
-
-            // short option
-            match short_name.len() {
-                0 => {}
-                1 => {
-                    row.push_char('-');
-                    row.push_str(short_name);
-                    row.push_char(' ');
-                }
-                _ => fail!("the short name should only be 1 ascii char long"),
+    /// Derive a usage message from a set of long options, wrapping the
+    /// descriptions so that lines are at most `width` characters long.
+    ///
+    /// Continuation lines are indented to the column the descriptions start
+    /// in. A word too long to fit is put on a line of its own.
+    pub fn usage_with_width(brief: &str, opts: &[OptGroup], width: uint) -> ~str {
+        let mut w = MemWriter::new();
+        write_usage_with_width(&mut w, brief, opts, width);
+        str::from_utf8_owned(w.inner())
+    }
+
+    /// Write the usage message `usage` would return to `w`, one option at
+    /// a time.
+    pub fn write_usage<W: Writer>(w: &mut W, brief: &str, opts: &[OptGroup]) {
+        write_usage_with_width(w, brief, opts, USAGE_WIDTH);
+    }
+
+    /// Write the usage message `usage_with_width` would return to `w`, one
+    /// option at a time.
+    pub fn write_usage_with_width<W: Writer>(w: &mut W, brief: &str, opts: &[OptGroup],
+                                             width: uint) {
+        w.write(brief.as_bytes());
+        w.write(bytes!("\n\nOptions:\n"));
+        write_options_table(w, opts, width);
+    }
+
+    /// Derive the table of options `usage` prints, without the brief and
+    /// the `Options:` line before it, for callers that format the rest of
+    /// the usage message themselves.
+    pub fn options_table(opts: &[OptGroup]) -> ~str {
+        let mut w = MemWriter::new();
+        write_options_table(&mut w, opts, USAGE_WIDTH);
+        str::from_utf8_owned(w.inner())
+    }
+
+    /// Write the table `options_table` would return to `w`, wrapped to
+    /// `width` characters as for `usage_with_width`.
+    pub fn write_options_table<W: Writer>(w: &mut W, opts: &[OptGroup], width: uint) {
+        for (i, optref) in opts.iter().enumerate() {
+            if i > 0 {
+                w.write(bytes!("\n"));
             }
+            w.write(format_option(optref, width).as_bytes());
+        }
+        w.write(bytes!("\n"));
+    }
 
-            // long option
-            match long_name.len() {
-                0 => {}
-                _ => {
-                    row.push_str("--");
-                    row.push_str(long_name);
-                    row.push_char(' ');
-                }
-            }
+    // The usage line for one option, with its description wrapped to fit
+    // within `width`.
+    fn format_option(optref: &OptGroup, width: uint) -> ~str {
+        let desc_sep = "\n" + " ".repeat(DESC_COLUMN);
 
-            // arg
-            match hasarg {
-                No => {}
-                Yes => row.push_str(hint),
-                Maybe => {
-                    row.push_char('[');
-                    row.push_str(hint);
-                    row.push_char(']');
-                }
+        let OptGroup{short_name: short_name,
+                     long_name: long_name,
+                     hint: hint,
+                     desc: desc,
+                     hasarg: hasarg,
+                     _} = (*optref).clone();
+
+        let mut row = " ".repeat(4);
+
+        // short option
+        match short_name.len() {
+            0 => {}
+            1 => {
+                row.push_char('-');
+                row.push_str(short_name);
+                row.push_char(' ');
             }
+            _ => fail!("the short name should only be 1 ascii char long"),
+        }
 
-            // FIXME: #5516 should be graphemes not codepoints
-            // here we just need to indent the start of the description
-            let rowlen = row.char_len();
-            if rowlen < 24 {
-                (24 - rowlen).times(|| {
-                    row.push_char(' ')
-                })
-            } else {
-                row.push_str(desc_sep)
+        // long option
+        match long_name.len() {
+            0 => {}
+            _ => {
+                row.push_str("--");
+                row.push_str(long_name);
+                row.push_char(' ');
             }
+        }
 
-            // Normalize desc to contain words separated by one space character
-            let mut desc_normalized_whitespace = ~"";
-            for word in desc.words() {
-                desc_normalized_whitespace.push_str(word);
-                desc_normalized_whitespace.push_char(' ');
+        // arg
+        match hasarg {
+            No => {}
+            Yes => row.push_str(hint),
+            Maybe => {
+                row.push_char('[');
+                row.push_str(hint);
+                row.push_char(']');
             }
+        }
 
-            // FIXME: #5516 should be graphemes not codepoints
-            let mut desc_rows = ~[];
-            each_split_within(desc_normalized_whitespace, 54, |substr| {
-                desc_rows.push(substr.to_owned());
-                true
-            });
+        // FIXME: #5516 should be graphemes not codepoints
+        // here we just need to indent the start of the description
+        let rowlen = row.char_len();
+        if rowlen < DESC_COLUMN {
+            (DESC_COLUMN - rowlen).times(|| {
+                row.push_char(' ')
+            })
+        } else {
+            row.push_str(desc_sep)
+        }
 
-            // FIXME: #5516 should be graphemes not codepoints
-            // wrapped description
-            row.push_str(desc_rows.connect(desc_sep));
+        // Normalize desc to contain words separated by one space character
+        let mut desc_normalized_whitespace = ~"";
+        for word in desc.words() {
+            desc_normalized_whitespace.push_str(word);
+            desc_normalized_whitespace.push_char(' ');
+        }
 
-            row
+        // FIXME: #5516 should be graphemes not codepoints
+        let mut desc_rows = ~[];
+        let desc_width = if width > DESC_COLUMN { width - DESC_COLUMN } else { 0 };
+        each_split_within(desc_normalized_whitespace, desc_width, |substr| {
+            desc_rows.push(substr.to_owned());
+            true
         });
 
-        format!("{}\n\nOptions:\n{}\n", brief, rows.collect::<~[~str]>().connect("\n"))
+        // FIXME: #5516 should be graphemes not codepoints
+        // wrapped description
+        row.push_str(desc_rows.connect(desc_sep));
+
+        row
     }
 
     /// Splits a string into substrings with possibly internal whitespace,
@@ -764,10 +1483,8 @@ pub mod groups {
     /// Note: Function was moved here from `std::str` because this module is the only place that
     /// uses it, and because it was to specific for a general string function.
     ///
-    /// #Failure:
-    ///
-    /// Fails during iteration if the string contains a non-whitespace
-    /// sequence longer than the limit.
+    /// A non-whitespace sequence longer than the limit is yielded on its own,
+    /// uncut, rather than being split or causing a failure.
     fn each_split_within<'a>(ss: &'a str, lim: uint, it: |&'a str| -> bool)
                          -> bool {
         // Just for fun, let's write this as a state machine:
@@ -810,9 +1527,8 @@ pub mod groups {
                 (A, Cr, _)        => { slice_start = i; last_start = i; B }
 
                 (B, Cr, UnderLim) => { B }
-                (B, Cr, OverLim)  if (i - last_start + 1) > lim
-                                => fail!("word starting with {} longer than limit!",
-                                        ss.slice(last_start, i + 1)),
+                // A word longer than the limit gets a line of its own.
+                (B, Cr, OverLim)  if (i - last_start + 1) > lim => { B }
                 (B, Cr, OverLim)  => { slice(); slice_start = last_start; B }
                 (B, Ws, UnderLim) => { last_end = i; C }
                 (B, Ws, OverLim)  => { last_end = i; slice(); A }
@@ -850,6 +1566,8 @@ pub mod groups {
             [~"Mary had a", ~"little lamb", ~"Little lamb"]);
         t("\nMary had a little lamb\nLittle lamb\n", ::std::uint::max_value,
             [~"Mary had a little lamb\nLittle lamb"]);
+        t("a verylongword b", 5, [~"a", ~"verylongword", ~"b"]);
+        t("verylongword", 0, [~"verylongword"]);
     }
 } // end groups module
 
@@ -858,7 +1576,9 @@ mod tests {
 
     use getopts::groups::OptGroup;
     use getopts::*;
+    use tempfile::TempDir;
 
+    use std::io::File;
     use std::result::{Err, Ok};
     use std::result;
 
@@ -868,7 +1588,14 @@ mod tests {
           UnrecognizedOption(_) => assert!(ft == UnrecognizedOption_),
           OptionMissing(_) => assert!(ft == OptionMissing_),
           OptionDuplicated(_) => assert!(ft == OptionDuplicated_),
-          UnexpectedArgument(_) => assert!(ft == UnexpectedArgument_)
+          UnexpectedArgument(_) => assert!(ft == UnexpectedArgument_),
+          ArgumentFileUnreadable(*) => assert!(ft == ArgumentFileUnreadable_),
+          ArgumentInvalid(*) => assert!(ft == ArgumentInvalid_),
+          OptionConflict(*) => assert!(ft == OptionConflict_),
+          SubcommandMissing => assert!(ft == SubcommandMissing_),
+          UnrecognizedSubcommand(_) => assert!(ft == UnrecognizedSubcommand_),
+          AmbiguousSubcommand(*) => assert!(ft == AmbiguousSubcommand_),
+          UnterminatedQuote(_) => assert!(ft == UnterminatedQuote_)
         }
     }
 
@@ -1442,6 +2169,458 @@ mod tests {
 
     }
 
+    #[test]
+    fn test_double_dash_ends_option_values() {
+        let args = ~[~"--files", ~"a", ~"--", ~"b"];
+        let opts = ~[optmulti("files")];
+        let rs = getopts(args, opts);
+        match rs {
+          Ok(ref m) => {
+            assert_eq!(m.opt_strs("files"), ~[~"a"]);
+            assert_eq!(m.free, ~[~"b"]);
+          }
+          _ => fail!()
+        }
+    }
+
+    #[test]
+    fn test_double_dash_forces_free() {
+        let args = ~[~"--", ~"--files", ~"-a"];
+        let opts = ~[optmulti("files"), optflag("a")];
+        let rs = getopts(args, opts);
+        match rs {
+          Ok(ref m) => {
+            assert!(!m.opt_present("files"));
+            assert!(!m.opt_present("a"));
+            assert_eq!(m.free, ~[~"--files", ~"-a"]);
+          }
+          _ => fail!()
+        }
+    }
+
+    #[test]
+    fn test_was_permuted() {
+        let opts = ~[optflag("a"), optopt("b")];
+
+        let args = ~[~"-a", ~"-b", ~"x", ~"free"];
+        let matches = getopts(args, opts).unwrap();
+        assert!(!matches.was_permuted());
+
+        let args = ~[~"free1", ~"-a", ~"free2", ~"-b", ~"x"];
+        let matches = getopts(args, opts).unwrap();
+        assert!(matches.was_permuted());
+        assert_eq!(matches.free, ~[~"free1", ~"free2"]);
+
+        // Arguments after `--` are never options.
+        let args = ~[~"free", ~"--", ~"-a"];
+        let matches = getopts(args, opts).unwrap();
+        assert!(!matches.was_permuted());
+    }
+
+    #[test]
+    fn test_file_backed_value() {
+        let dir = TempDir::new("getopts").unwrap();
+        let path = dir.path().join("keyfile");
+        File::create(&path).write("secret".as_bytes());
+
+        let opts = ~[optopt("key").file_backed(), optopt("name")];
+
+        let args = ~[~"--key", format!("@{}", path.display()),
+                     ~"--name", ~"@literal"];
+        let matches = getopts(args, opts).unwrap();
+        assert_eq!(matches.opt_str("key").unwrap(), ~"secret");
+        assert_eq!(matches.opt_str("name").unwrap(), ~"@literal");
+
+        // Values without the `@` prefix are taken literally
+        let args = ~[~"--key", ~"plain"];
+        let matches = getopts(args, opts).unwrap();
+        assert_eq!(matches.opt_str("key").unwrap(), ~"plain");
+
+        // Both names of a group read the file
+        let opts = ~[groups::optopt("k", "key", "the key", "KEY").file_backed()];
+        for name in [~"-k", ~"--key"].iter() {
+            let args = ~[name.clone(), format!("@{}", path.display())];
+            let matches = groups::getopts(args, opts).unwrap();
+            assert_eq!(matches.opt_str("key").unwrap(), ~"secret");
+        }
+    }
+
+    #[test]
+    fn test_file_backed_value_missing() {
+        let dir = TempDir::new("getopts").unwrap();
+        let path = dir.path().join("missing");
+
+        let opts = ~[optopt("key").file_backed()];
+
+        let args = ~[~"--key", format!("@{}", path.display())];
+        match getopts(args, opts) {
+          Err(f) => {
+            check_fail_type(f.clone(), ArgumentFileUnreadable_);
+            assert_eq!(f.to_err_msg(),
+                       format!("Argument file '{}' for option 'key' could \
+                                not be read.", path.display()));
+          }
+          _ => fail!()
+        }
+    }
+
+    #[test]
+    fn test_free_sorted() {
+        let args = ~[~"pear", ~"-a", ~"apple", ~"fig", ~"apple"];
+        let opts = ~[optflag("a")];
+        let matches = getopts(args, opts).unwrap();
+        assert_eq!(matches.free_sorted(),
+                   ~[~"apple", ~"apple", ~"fig", ~"pear"]);
+        assert_eq!(matches.free, ~[~"pear", ~"apple", ~"fig", ~"apple"]);
+    }
+
+    #[test]
+    fn test_reject_duplicate_free() {
+        let opts = ~[optflag("a")];
+        let matches = getopts([~"x", ~"-a", ~"y"], opts).unwrap();
+        assert!(matches.reject_duplicate_free().is_ok());
+
+        let matches = getopts([~"x", ~"y", ~"-a", ~"y", ~"x"], opts).unwrap();
+        assert_eq!(matches.reject_duplicate_free().unwrap_err(),
+                   ~"Argument 'y' given more than once.");
+    }
+
+    #[test]
+    fn test_terminator() {
+        let opts = ~[optterminator("args"), optflag("v")];
+        let matches = getopts([~"--args", ~"-x", ~"y"], opts).unwrap();
+        assert!(matches.opt_present("args"));
+        assert_eq!(matches.free, ~[~"-x", ~"y"]);
+
+        let matches = getopts([~"a", ~"-v", ~"--args", ~"--", ~"-v"], opts).unwrap();
+        assert!(matches.opt_present("v"));
+        assert_eq!(matches.free, ~[~"a", ~"--", ~"-v"]);
+
+        let opts = ~[groups::optterminator("", "args", "pass the rest on")];
+        let matches = groups::getopts([~"--args", ~"-x", ~"y"], opts).unwrap();
+        assert_eq!(matches.free, ~[~"-x", ~"y"]);
+    }
+
+    #[test]
+    fn test_double_dash_once() {
+        let opts = ~[optflag("v")];
+        let matches = getopts([~"--", ~"--", ~"x"], opts).unwrap();
+        assert_eq!(matches.free, ~[~"--", ~"x"]);
+
+        let matches = getopts([~"-v", ~"--", ~"-v", ~"--"], opts).unwrap();
+        assert_eq!(matches.opt_count("v"), 1);
+        assert_eq!(matches.free, ~[~"-v", ~"--"]);
+    }
+
+    #[test]
+    fn test_free_groups() {
+        let opts = ~[optflag("v")];
+        let matches = getopts([~"--", ~"a", ~"b", ~"--", ~"c", ~"d"], opts).unwrap();
+        assert_eq!(matches.free_groups(), ~[~[~"a", ~"b"], ~[~"c", ~"d"]]);
+
+        let matches = getopts([~"x", ~"-v", ~"--", ~"a", ~"--", ~"--"], opts).unwrap();
+        assert_eq!(matches.free_groups(), ~[~[~"x"], ~[~"a"]]);
+
+        let matches = getopts([~"x", ~"y"], opts).unwrap();
+        assert_eq!(matches.free_groups(), ~[~[~"x", ~"y"]]);
+    }
+
+    #[test]
+    fn test_subcommand() {
+        let opts = ~[optflag("v"), optopt("C")];
+        let commands = ["build", "bench", "test", "testall"];
+        let parse = |args: ~[~str]| getopts_subcommand(args, opts, commands);
+
+        // An unambiguous prefix selects the command.
+        let (m, name, rest) = parse(~[~"-v", ~"-C", ~"dir", ~"bu", ~"-x", ~"y"]).unwrap();
+        assert!(m.opt_present("v"));
+        assert_eq!(m.opt_str("C"), Some(~"dir"));
+        assert_eq!(name, ~"build");
+        assert_eq!(rest, ~[~"-x", ~"y"]);
+
+        // An exact match wins over longer commands sharing the prefix.
+        let (_, name, rest) = parse(~[~"--", ~"test"]).unwrap();
+        assert_eq!(name, ~"test");
+        assert!(rest.is_empty());
+
+        match parse(~[~"b"]) {
+            Err(f) => {
+                assert_eq!(f.clone(), AmbiguousSubcommand(~"b", ~[~"build", ~"bench"]));
+                assert_eq!(f.to_err_msg(),
+                           ~"Command 'b' is ambiguous; it could be 'build', 'bench'.");
+            }
+            _ => fail!()
+        }
+        match parse(~[~"run"]) {
+            Err(f) => check_fail_type(f, UnrecognizedSubcommand_),
+            _ => fail!()
+        }
+        match parse(~[~"-v"]) {
+            Err(f) => check_fail_type(f, SubcommandMissing_),
+            _ => fail!()
+        }
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let opts = ~[optflag("quiet").conflicts_with(["v"]),
+                     optflag("v").conflicts_with(["quiet"]),
+                     optopt("o")];
+        match getopts([~"-v", ~"--quiet"], opts) {
+            Err(f) => {
+                assert_eq!(f.clone(), OptionConflict(~"quiet", ~"v"));
+                assert_eq!(f.to_err_msg(),
+                           ~"Options 'quiet' and 'v' cannot be used together.");
+            }
+            _ => fail!()
+        }
+        match validate_all([~"-v", ~"--quiet"], opts) {
+            Err(errs) => assert_eq!(errs.len(), 1),
+            _ => fail!()
+        }
+
+        let matches = getopts([~"-v", ~"-o", ~"x"], opts).unwrap();
+        assert!(matches.opt_present("v"));
+        assert!(!matches.opt_present("quiet"));
+
+        let opts = ~[groups::optflag("q", "quiet", "").conflicts_with(["v"]),
+                     groups::optflag("v", "verbose", "")];
+        match groups::getopts([~"-q", ~"--verbose"], opts) {
+            Err(f) => assert_eq!(f, OptionConflict(~"quiet", ~"verbose")),
+            _ => fail!()
+        }
+    }
+
+    #[test]
+    fn test_optflagopt_presence() {
+        let opts = ~[optflagopt("color")];
+
+        let bare = getopts([~"--color"], opts).unwrap();
+        assert!(bare.opt_present("color"));
+        assert_eq!(bare.opt_str("color"), None);
+        assert_eq!(bare.opt_default("color", "auto"), Some(~"auto"));
+
+        let valued = getopts([~"--color=x"], opts).unwrap();
+        assert!(valued.opt_present("color"));
+        assert_eq!(valued.opt_str("color"), Some(~"x"));
+        assert_eq!(valued.opt_default("color", "auto"), Some(~"x"));
+
+        let absent = getopts([~"file"], opts).unwrap();
+        assert!(!absent.opt_present("color"));
+        assert_eq!(absent.opt_str("color"), None);
+        assert_eq!(absent.opt_default("color", "auto"), None);
+    }
+
+    #[test]
+    fn test_complete() {
+        let opts = ~[optflag("version"), optflag("verbose"), optopt("o"),
+                     groups::optflag("i", "visual", "").long_to_short()];
+        assert_eq!(complete(opts, "--ver"), ~[~"--verbose", ~"--version"]);
+        assert_eq!(complete(opts, "--vis"), ~[~"--visual"]);
+        assert_eq!(complete(opts, "-i"), ~[~"-i"]);
+        assert_eq!(complete(opts, "-o"), ~[~"-o"]);
+        assert_eq!(complete(opts, "--x"), ~[]);
+        assert_eq!(complete(opts, "-").len(), 5);
+    }
+
+    #[test]
+    fn test_separated_by() {
+        let opts = ~[optmulti("path").separated_by(':'), optmulti("name")];
+        let args = ~[~"--path", ~"a:b:c", ~"--path=d", ~"--name", ~"x:y"];
+        let matches = getopts(args, opts).unwrap();
+        assert_eq!(matches.opt_strs("path"), ~[~"a", ~"b", ~"c", ~"d"]);
+        // Options without a separator are not split.
+        assert_eq!(matches.opt_strs("name"), ~[~"x:y"]);
+
+        let matches = getopts([~"--path", ~"a::b"], opts).unwrap();
+        assert_eq!(matches.opt_strs("path"), ~[~"a", ~"", ~"b"]);
+
+        let opts = ~[groups::optmulti("p", "path", "", "DIRS").separated_by(':')];
+        let matches = groups::getopts([~"-p", ~"a:b"], opts).unwrap();
+        assert_eq!(matches.opt_strs("p"), ~[~"a", ~"b"]);
+        assert_eq!(matches.opt_strs("path"), ~[~"a", ~"b"]);
+    }
+
+    #[test]
+    fn test_getopts_from_str() {
+        let opts = ~[optopt("o"), optflag("v"), optopt("name")];
+
+        let m = getopts_from_str("-v  -o out.txt in1 in2", opts).unwrap();
+        assert!(m.opt_present("v"));
+        assert_eq!(m.opt_str("o"), Some(~"out.txt"));
+        assert_eq!(m.free, ~[~"in1", ~"in2"]);
+
+        let m = getopts_from_str("--name \"John \\\"J\\\" Doe\" a\\ b \"\"", opts).unwrap();
+        assert_eq!(m.opt_str("name"), Some(~"John \"J\" Doe"));
+        assert_eq!(m.free, ~[~"a b", ~""]);
+
+        match getopts_from_str("-o \"out.txt", opts) {
+          Err(f) => {
+            assert_eq!(f.clone().to_err_msg(),
+                       ~"Unterminated quote in command line: -o \"out.txt");
+            check_fail_type(f, UnterminatedQuote_);
+          }
+          _ => fail!()
+        }
+    }
+
+    #[test]
+    fn test_free_int() {
+        let opts = ~[optflag("v")];
+        let matches = getopts([~"3", ~"-v", ~"-12", ~"three", ~"1,000"], opts).unwrap();
+        assert_eq!(matches.free_int(0), Some(Ok(3)));
+        assert_eq!(matches.free_int(1), Some(Ok(-12)));
+        assert_eq!(matches.free_int(2), Some(Err(~"Free argument 2 ('three') is invalid.")));
+        assert!(matches.free_int(3).unwrap().is_err());
+        assert_eq!(matches.free_int(4), None);
+    }
+
+    #[test]
+    fn test_opt_map() {
+        let opts = ~[optmulti("set")];
+        let args = ~[~"--set", ~"k2=v2", ~"--set", ~"k1=v1", ~"--set", ~"url=a=b",
+                     ~"--set", ~"novalue", ~"--set", ~"k2=again"];
+        let matches = getopts(args, opts).unwrap();
+        assert_eq!(matches.opt_map("set"),
+                   ~[(~"k2", ~"v2"), (~"k1", ~"v1"), (~"url", ~"a=b"),
+                     (~"novalue", ~""), (~"k2", ~"again")]);
+
+        // The attached form keeps everything after the first `=`.
+        let args = ~[~"--set=k=v=w", ~"--set=url=a=b", ~"--set=plain"];
+        let matches = getopts(args, opts).unwrap();
+        assert_eq!(matches.opt_map("set"),
+                   ~[(~"k", ~"v=w"), (~"url", ~"a=b"), (~"plain", ~"")]);
+
+        let matches = getopts([], opts).unwrap();
+        assert!(matches.opt_map("set").is_empty());
+    }
+
+    #[test]
+    fn test_max_occurs() {
+        let opts = ~[optmultimax("I", 3)];
+        let parse = |args: ~[~str]| getopts(args, opts);
+
+        assert_eq!(parse(~[~"-Ia", ~"-Ib"]).unwrap().opt_strs("I"), ~[~"a", ~"b"]);
+        assert_eq!(parse(~[~"-Ia", ~"-Ib", ~"-Ic"]).unwrap().opt_strs("I").len(), 3);
+        match parse(~[~"-Ia", ~"-Ib", ~"-Ic", ~"-Id"]) {
+            Err(f) => check_fail_type(f, OptionDuplicated_),
+            _ => fail!()
+        }
+
+        let opts = ~[groups::optmultimax("I", "include", "a directory", "DIR", 1)];
+        assert!(groups::getopts([~"--include", ~"a"], opts).is_ok());
+        assert!(groups::getopts([~"-Ia", ~"--include=b"], opts).is_err());
+    }
+
+    #[test]
+    fn test_validate_all() {
+        let args = ~[~"--unknown", ~"-v"];
+        let opts = ~[reqopt("test"), optflag("v")];
+        match validate_all(args, opts) {
+          Err(errs) => {
+            assert_eq!(errs, ~[UnrecognizedOption(~"unknown"),
+                               OptionMissing(~"test")]);
+          }
+          _ => fail!()
+        }
+        match getopts(args, opts) {
+          Err(f) => check_fail_type(f, UnrecognizedOption_),
+          _ => fail!()
+        }
+        match validate_all([~"--test=x"], opts) {
+          Ok(ref m) => assert_eq!(m.opt_str("test"), Some(~"x")),
+          _ => fail!()
+        }
+    }
+
+    #[test]
+    fn test_level_counting() {
+        let opts = ~[groups::optlevel("v", "verbose", "more output", "N")];
+        let args = ~[~"-vvv", ~"file", ~"--verbose"];
+        let matches = groups::getopts(args, opts).unwrap();
+        assert_eq!(matches.opt_level("v"), 4);
+        assert_eq!(matches.opt_level("verbose"), 4);
+        assert_eq!(matches.free, ~[~"file"]);
+
+        let matches = groups::getopts([], opts).unwrap();
+        assert_eq!(matches.opt_level("v"), 0);
+    }
+
+    #[test]
+    fn test_level_explicit() {
+        let opts = ~[groups::optlevel("v", "verbose", "more output", "N")];
+        let args = ~[~"--verbose=2", ~"--verbose", ~"5"];
+        let matches = groups::getopts(args, opts).unwrap();
+        assert_eq!(matches.opt_level("verbose"), 2);
+        assert_eq!(matches.free, ~[~"5"]);
+
+        let args = ~[~"--verbose=two"];
+        check_fail_type(groups::getopts(args, opts).unwrap_err(),
+                        ArgumentInvalid_);
+    }
+
+    #[test]
+    fn test_level_mixed() {
+        let opts = ~[optlevel("v"), optlevel("verbose")];
+        let args = ~[~"-vv", ~"--verbose=1", ~"-v", ~"--verbose=3",
+                     ~"--verbose"];
+        let matches = getopts(args, opts).unwrap();
+        assert_eq!(matches.opt_level("v"), 3);
+        assert_eq!(matches.opt_level("verbose"), 3);
+
+        let opts = ~[groups::optlevel("v", "verbose", "more output", "N")];
+        let args = ~[~"-vvvv", ~"--verbose=1", ~"-v"];
+        let matches = groups::getopts(args, opts).unwrap();
+        assert_eq!(matches.opt_level("v"), 1);
+    }
+
+    #[test]
+    fn test_opt_get() {
+        let opts = ~[optopt("n"), optopt("ratio"), optopt("flag"), optopt("x")];
+        let args = ~[~"-n", ~"-12", ~"--ratio=0.5", ~"--flag", ~"maybe"];
+        let matches = getopts(args, opts).unwrap();
+        assert_eq!(matches.opt_get::<int>("n"), Some(Ok(-12)));
+        assert_eq!(matches.opt_get::<f64>("ratio"), Some(Ok(0.5)));
+        assert_eq!(matches.opt_get::<bool>("flag"),
+                   Some(Err(~"Argument 'maybe' to option 'flag' is invalid.")));
+        assert_eq!(matches.opt_get::<int>("x"), None);
+    }
+
+    #[test]
+    fn test_opt_int_float() {
+        let opts = ~[optopt("n"), optopt("m"), optopt("f"), optopt("g")];
+        let args = ~[~"-n", ~"1000", ~"-m", ~"1,000", ~"-f", ~"1.5", ~"-g", ~"1,5"];
+        let matches = getopts(args, opts).unwrap();
+        assert_eq!(matches.opt_int("n"), Some(Ok(1000)));
+        assert!(matches.opt_int("m").unwrap().is_err());
+        assert!(matches.opt_int("f").unwrap().is_err());
+        assert_eq!(matches.opt_float("f"), Some(Ok(1.5)));
+        assert_eq!(matches.opt_float("n"), Some(Ok(1000.0)));
+        assert!(matches.opt_float("g").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_negative_numbers() {
+        let opts = ~[optopt("o"), optflagopt("m"), optflag("1")];
+        let args = ~[~"-5", ~"-o", ~"-5", ~"-m", ~"-42", ~"-1"];
+        let matches = getopts(args, opts).unwrap();
+        assert_eq!(matches.free, ~[~"-5"]);
+        assert_eq!(matches.opt_str("o"), Some(~"-5"));
+        assert_eq!(matches.opt_str("m"), Some(~"-42"));
+        // `-1` names a declared option, so it is not a number.
+        assert!(matches.opt_present("1"));
+
+        let args = ~[~"-m", ~"-1"];
+        let matches = getopts(args, opts).unwrap();
+        assert!(matches.opt_present("m"));
+        assert_eq!(matches.opt_str("m"), None);
+        assert!(matches.opt_present("1"));
+
+        // Only ASCII digits make a number, as for `opt_int`, so this is an
+        // unknown option rather than a free argument.
+        assert!(getopts([~"-\u0663"], opts).is_err());
+    }
+
     #[test]
     fn test_groups_reqopt() {
         let opt = groups::reqopt("b", "banana", "some bananas", "VAL");
@@ -1450,7 +2629,13 @@ mod tests {
                         hint: ~"VAL",
                         desc: ~"some bananas",
                         hasarg: Yes,
-                        occur: Req })
+                        occur: Req,
+                        level: false,
+                        terminator: false,
+                        max_occurs: None,
+                        conflicts: ~[],
+                        separator: None,
+                        file_backed: false })
     }
 
     #[test]
@@ -1461,7 +2646,13 @@ mod tests {
                         hint: ~"VAL",
                         desc: ~"some apples",
                         hasarg: Yes,
-                        occur: Optional })
+                        occur: Optional,
+                        level: false,
+                        terminator: false,
+                        max_occurs: None,
+                        conflicts: ~[],
+                        separator: None,
+                        file_backed: false })
     }
 
     #[test]
@@ -1472,7 +2663,13 @@ mod tests {
                         hint: ~"",
                         desc: ~"some kiwis",
                         hasarg: No,
-                        occur: Optional })
+                        occur: Optional,
+                        level: false,
+                        terminator: false,
+                        max_occurs: None,
+                        conflicts: ~[],
+                        separator: None,
+                        file_backed: false })
     }
 
     #[test]
@@ -1483,7 +2680,13 @@ mod tests {
                         hint: ~"VAL",
                         desc: ~"some pineapples",
                         hasarg: Maybe,
-                        occur: Optional })
+                        occur: Optional,
+                        level: false,
+                        terminator: false,
+                        max_occurs: None,
+                        conflicts: ~[],
+                        separator: None,
+                        file_backed: false })
     }
 
     #[test]
@@ -1494,7 +2697,13 @@ mod tests {
                         hint: ~"VAL",
                         desc: ~"some limes",
                         hasarg: Yes,
-                        occur: Multi })
+                        occur: Multi,
+                        level: false,
+                        terminator: false,
+                        max_occurs: None,
+                        conflicts: ~[],
+                        separator: None,
+                        file_backed: false })
     }
 
     #[test]
@@ -1581,25 +2790,47 @@ Options:
         assert_eq!(generated_usage, expected);
     }
 
+    #[test]
+    fn test_groups_write_usage() {
+        use std::io::Decorator;
+        use std::io::mem::MemWriter;
+        use std::str;
+
+        let optgroups = ~[
+            groups::reqopt("b", "banana", "Desc", "VAL"),
+            groups::optflag("k", "kiwi", "A description long enough to be wrapped \
+                                          over more than one line of the output"),
+        ];
+
+        let mut w = MemWriter::new();
+        groups::write_usage(&mut w, "Usage: fruits", optgroups);
+        assert_eq!(str::from_utf8_owned(w.inner()),
+                   groups::usage("Usage: fruits", optgroups));
+
+        let mut w = MemWriter::new();
+        groups::write_usage(&mut w, "Usage: nothing", []);
+        assert_eq!(str::from_utf8_owned(w.inner()), ~"Usage: nothing\n\nOptions:\n\n");
+    }
+
     #[test]
     fn test_groups_usage_description_wrapping() {
         // indentation should be 24 spaces
-        // lines wrap after 78: or rather descriptions wrap after 54
+        // lines wrap after 80: or rather descriptions wrap after 56
 
         let optgroups = ~[
             groups::optflag("k", "kiwi",
-                "This is a long description which won't be wrapped..+.."), // 54
+                "This is a long description which won't be wrapped..+...."), // 56
             groups::optflag("a", "apple",
-                "This is a long description which _will_ be wrapped..+.."), // 55
+                "This is a long description which _will_ be wrapped..+...."), // 57
         ];
 
         let expected =
 ~"Usage: fruits
 
 Options:
-    -k --kiwi           This is a long description which won't be wrapped..+..
+    -k --kiwi           This is a long description which won't be wrapped..+....
     -a --apple          This is a long description which _will_ be
-                        wrapped..+..
+                        wrapped..+....
 ";
 
         let usage = groups::usage("Usage: fruits", optgroups);
@@ -1609,6 +2840,55 @@ Options:
         assert!(usage == expected)
     }
 
+    #[test]
+    fn test_groups_usage_with_width() {
+        let optgroups = ~[
+            groups::optflag("k", "kiwi",
+                "A description which wraps at forty columns, with an \
+                 unbreakable_word_much_longer_than_the_width in it"),
+        ];
+
+        let expected =
+~"Usage: fruits
+
+Options:
+    -k --kiwi           A description
+                        which wraps at
+                        forty columns,
+                        with an
+                        unbreakable_word_much_longer_than_the_width
+                        in it
+";
+
+        let usage = groups::usage_with_width("Usage: fruits", optgroups, 40);
+
+        debug!("expected: <<{}>>", expected);
+        debug!("generated: <<{}>>", usage);
+        assert_eq!(usage, expected);
+    }
+
+    #[test]
+    fn test_groups_options_table() {
+        let optgroups = ~[
+            groups::reqopt("b", "banana", "Desc", "VAL"),
+            groups::optflag("k", "kiwi", "Desc"),
+        ];
+
+        let expected =
+~"    -b --banana VAL     Desc
+    -k --kiwi           Desc
+";
+
+        let table = groups::options_table(optgroups);
+        debug!("expected: <<{}>>", expected);
+        debug!("generated: <<{}>>", table);
+        assert_eq!(table, expected);
+        assert!(!table.contains("Options:"));
+
+        let usage = groups::usage("Usage: fruits", optgroups);
+        assert_eq!(usage, ~"Usage: fruits\n\nOptions:\n" + expected);
+    }
+
     #[test]
     fn test_groups_usage_description_multibyte_handling() {
         let optgroups = ~[
@@ -18,6 +18,7 @@
 
 use std::char;
 use std::cast::transmute;
+use std::error;
 use std::f64;
 use std::hashmap::HashMap;
 use std::io;
@@ -1331,6 +1332,10 @@ impl to_str::ToStr for Error {
     }
 }
 
+impl error::Error for Error {
+    fn description<'a>(&'a self) -> &'a str { self.msg.as_slice() }
+}
+
 #[cfg(test)]
 mod tests {
 
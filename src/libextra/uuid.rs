@@ -65,6 +65,7 @@ use std::rand;
 use std::rand::Rng;
 use std::cmp::Eq;
 use std::cast::{transmute,transmute_copy};
+use std::error::Error;
 
 use serialize::{Encoder, Encodable, Decoder, Decodable};
 
@@ -147,6 +148,17 @@ impl ToStr for ParseError {
     }
 }
 
+impl Error for ParseError {
+    fn description<'a>(&'a self) -> &'a str {
+        match *self {
+            ErrorInvalidLength(*) => "Invalid length",
+            ErrorInvalidCharacter(*) => "Invalid character",
+            ErrorInvalidGroups(*) => "Malformed; wrong number of groups",
+            ErrorInvalidGroupLength(*) => "Malformed; wrong group length",
+        }
+    }
+}
+
 // Length of each hyphenated group in hex digits
 static UuidGroupLens: [uint, ..5] = [8u, 4u, 4u, 4u, 12u];
 
@@ -231,8 +231,8 @@ pub fn phase_3_run_analysis_passes(sess: Session,
                               sess.opts.is_static,
                               token::get_ident_interner()));
 
-    let lang_items = time(time_passes, "language item collection", (), |_|
-                          middle::lang_items::collect_language_items(crate, sess));
+    let lang_items = @time(time_passes, "language item collection", (), |_|
+                           middle::lang_items::collect_language_items(crate, sess));
 
     let middle::resolve::CrateMap {
         def_map: def_map,
@@ -250,6 +250,9 @@ pub fn phase_3_run_analysis_passes(sess: Session,
     time(time_passes, "looking for entry point", (),
          |_| middle::entry::find_entry_point(sess, crate, ast_map));
 
+    time(time_passes, "looking for global allocator", (),
+         |_| middle::global_allocator::find_global_allocator(sess, crate));
+
     let freevars = time(time_passes, "freevar finding", (), |_|
                         freevars::annotate_freevars(def_map, crate));
 
@@ -310,7 +313,7 @@ pub fn phase_3_run_analysis_passes(sess: Session,
              reachable::find_reachable(ty_cx, method_map, &exported_items));
 
     time(time_passes, "lint checking", (), |_|
-         lint::check_crate(ty_cx, &exported_items, crate));
+         lint::check_crate(ty_cx, method_map, &exported_items, crate));
 
     CrateAnalysis {
         exp_map2: exp_map2,
@@ -844,6 +847,7 @@ pub fn build_session_(sopts: @session::options,
         // For a library crate, this is always none
         entry_fn: @mut None,
         entry_type: @mut None,
+        global_allocator: @mut None,
         span_diagnostic: span_diagnostic_handler,
         filesearch: filesearch,
         building_library: @mut false,
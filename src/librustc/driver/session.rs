@@ -77,6 +77,7 @@ pub static no_vectorize_slp:        uint = 1 << 27;
 pub static no_prepopulate_passes:   uint = 1 << 28;
 pub static use_softfp:              uint = 1 << 29;
 pub static gen_crate_map:           uint = 1 << 30;
+pub static lang_item_crate_type:    uint = 1 << 31;
 
 pub fn debugging_opts_map() -> ~[(&'static str, &'static str, uint)] {
     ~[("verbose", "in general, enable more debug printouts", verbose),
@@ -130,6 +131,9 @@ pub fn debugging_opts_map() -> ~[(&'static str, &'static str, uint)] {
       no_vectorize_slp),
      ("soft-float", "Generate software floating point library calls", use_softfp),
      ("gen-crate-map", "Force generation of a toplevel crate map", gen_crate_map),
+     ("lang-item-crate-type",
+      "Warn about lang items defined in a crate type that cannot use them",
+      lang_item_crate_type),
     ]
 }
 
@@ -204,6 +208,8 @@ pub struct Session_ {
     // For a library crate, this is always none
     entry_fn: @mut Option<(NodeId, codemap::Span)>,
     entry_type: @mut Option<EntryFnType>,
+    // The static marked `#[global_allocator]`, if any
+    global_allocator: @mut Option<(NodeId, codemap::Span)>,
     span_diagnostic: @mut diagnostic::span_handler,
     filesearch: @filesearch::FileSearch,
     building_library: @mut bool,
@@ -74,6 +74,7 @@ pub mod middle {
     pub mod privacy;
     pub mod moves;
     pub mod entry;
+    pub mod global_allocator;
     pub mod effect;
     pub mod reachable;
     pub mod graph;
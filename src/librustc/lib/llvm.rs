@@ -1025,6 +1025,9 @@ pub mod llvm {
         pub fn LLVMBuildStore(B: BuilderRef, Val: ValueRef, Ptr: ValueRef)
                               -> ValueRef;
 
+        /// Marks a load or store instruction as volatile.
+        pub fn LLVMSetVolatile(MemoryAccessInst: ValueRef, volatile: Bool);
+
         pub fn LLVMBuildGEP(B: BuilderRef,
                             Pointer: ValueRef,
                             Indices: *ValueRef,
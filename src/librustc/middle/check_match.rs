@@ -24,7 +24,7 @@ use std::vec;
 use extra::sort;
 use syntax::ast::*;
 use syntax::ast_util::{unguarded_pat, walk_pat};
-use syntax::codemap::{Span, dummy_sp, Spanned};
+use syntax::codemap::{Span, dummy_sp};
 use syntax::visit;
 use syntax::visit::{Visitor,fn_kind};
 
@@ -814,7 +814,7 @@ fn check_local(v: &mut CheckMatchVisitor,
                    loc: @Local,
                    s: ()) {
     visit::walk_local(v, loc, s);
-    if is_refutable(cx, loc.pat) {
+    if !pat_is_irrefutable(cx.tcx, loc.pat) {
         cx.tcx.sess.span_err(loc.pat.span,
                              "refutable pattern in local binding");
     }
@@ -833,49 +833,13 @@ fn check_fn(v: &mut CheckMatchVisitor,
                 s: ()) {
     visit::walk_fn(v, kind, decl, body, sp, id, s);
     for input in decl.inputs.iter() {
-        if is_refutable(cx, input.pat) {
+        if !pat_is_irrefutable(cx.tcx, input.pat) {
             cx.tcx.sess.span_err(input.pat.span,
                                  "refutable pattern in function argument");
         }
     }
 }
 
-fn is_refutable(cx: &MatchCheckCtxt, pat: &Pat) -> bool {
-    match cx.tcx.def_map.find(&pat.id) {
-      Some(&DefVariant(enum_id, _, _)) => {
-        if ty::enum_variants(cx.tcx, enum_id).len() != 1u {
-            return true;
-        }
-      }
-      Some(&DefStatic(*)) => return true,
-      _ => ()
-    }
-
-    match pat.node {
-      PatBox(sub) | PatUniq(sub) | PatRegion(sub) |
-      PatIdent(_, _, Some(sub)) => {
-        is_refutable(cx, sub)
-      }
-      PatWild | PatWildMulti | PatIdent(_, _, None) => { false }
-      PatLit(@Expr {node: ExprLit(@Spanned { node: lit_nil, _}), _}) => {
-        // "()"
-        false
-      }
-      PatLit(_) | PatRange(_, _) => { true }
-      PatStruct(_, ref fields, _) => {
-        fields.iter().any(|f| is_refutable(cx, f.pat))
-      }
-      PatTup(ref elts) => {
-        elts.iter().any(|elt| is_refutable(cx, *elt))
-      }
-      PatEnum(_, Some(ref args)) => {
-        args.iter().any(|a| is_refutable(cx, *a))
-      }
-      PatEnum(_,_) => { false }
-      PatVec(*) => { true }
-    }
-}
-
 // Legality of move bindings checking
 
 fn check_legality_of_move_bindings(cx: &MatchCheckCtxt,
@@ -9,7 +9,10 @@
 // except according to those terms.
 
 //! Enforces the Rust effect system. Currently there is just one effect,
-/// `unsafe`.
+//! `unsafe`.
+//!
+//! Every `unsafe` block containing an operation that needs it is recorded
+//! in `tcx.used_unsafe`. The `unused_unsafe` lint warns about the others.
 
 use middle::ty;
 use middle::typeck::method_map;
@@ -0,0 +1,62 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Finds the static marked `#[global_allocator]`, which selects the
+// `std::alloc::GlobalAlloc` implementation the crate wants to allocate with,
+// and records it in the session.
+
+use driver::session::Session;
+use syntax::ast::{Crate, NodeId, item, item_static};
+use syntax::attr;
+use syntax::codemap::Span;
+use syntax::visit;
+use syntax::visit::Visitor;
+
+struct GlobalAllocatorContext {
+    session: Session,
+
+    // The first static found with the attribute
+    allocator: Option<(NodeId, Span)>,
+}
+
+impl Visitor<()> for GlobalAllocatorContext {
+    fn visit_item(&mut self, item: @item, _: ()) {
+        if attr::contains_name(item.attrs, "global_allocator") {
+            match item.node {
+                item_static(*) => {
+                    if self.allocator.is_none() {
+                        self.allocator = Some((item.id, item.span));
+                    } else {
+                        self.session.span_err(item.span,
+                                              "multiple `#[global_allocator]` statics");
+                    }
+                }
+                _ => {
+                    self.session.span_err(item.span,
+                                          "`#[global_allocator]` can only be applied \
+                                           to a static");
+                }
+            }
+        }
+
+        visit::walk_item(self, item, ());
+    }
+}
+
+pub fn find_global_allocator(session: Session, crate: &Crate) {
+    let mut ctxt = GlobalAllocatorContext {
+        session: session,
+        allocator: None,
+    };
+
+    visit::walk_crate(&mut ctxt, crate, ());
+
+    *session.global_allocator = ctxt.allocator;
+}
@@ -21,19 +21,25 @@
 
 
 use driver::session::Session;
-use metadata::csearch::each_lang_item;
+use driver::session;
+use metadata::csearch::{each_lang_item, get_method_name_and_explicit_self};
+use metadata::csearch::get_trait_method_def_ids;
 use metadata::cstore::iter_crate_data;
 use middle::ty::{BuiltinBound, BoundFreeze, BoundSend, BoundSized};
 use syntax::ast;
 use syntax::ast_util::local_def;
 use syntax::attr::AttrMetaMethods;
+use syntax::attr;
+use syntax::codemap::Span;
 use syntax::visit;
 use syntax::visit::Visitor;
 
+use std::cell::RefCell;
 use std::hashmap::HashMap;
 use std::iter::Enumerate;
 use std::vec;
 
+#[deriving(Eq)]
 pub enum LangItem {
     FreezeTraitLangItem,               // 0
     SendTraitLangItem,                 // 1
@@ -82,23 +88,355 @@ pub enum LangItem {
     OpaqueStructLangItem,              // 38
 
     EventLoopFactoryLangItem,          // 39
+
+    DefaultTraitLangItem,              // 40
+
+    FnTraitLangItem,                   // 41
+
+    DropFlagLangItem,                  // 42
+
+    CopyTraitLangItem,                 // 43
+
+    IndexMutTraitLangItem,             // 44
+
+    AddWithOverflowFnLangItem,         // 45
+    SubWithOverflowFnLangItem,         // 46
+    MulWithOverflowFnLangItem,         // 47
+}
+
+/// The number of language items; the length of `LanguageItems::items`.
+pub static NUM_LANG_ITEMS: uint = 48;
+
+/// A coarse grouping of language items by the role they play.
+#[deriving(Eq)]
+pub enum LangItemCategory {
+    /// Traits that specify kinds (`Freeze`, `Send`, `Sized`).
+    KindCategory,
+    /// Traits that overloaded operators desugar to.
+    OperatorCategory,
+    /// Traits backing the comparison operators.
+    ComparisonCategory,
+    /// Other traits the compiler calls into (`Drop`, `Default`).
+    TraitCategory,
+    /// Functions the compiler emits calls to at runtime.
+    RuntimeCategory,
+    /// Allocation and deallocation functions.
+    AllocationCategory,
+    /// Functions used to dynamically check `@mut` borrows.
+    BorrowCategory,
+    /// Items used by reflection and type descriptors.
+    ReflectionCategory,
+}
+
+impl LangItem {
+    /// Returns the language item with the given index, i.e. the inverse of
+    /// `it as uint`.
+    pub fn from_uint(index: uint) -> Option<LangItem> {
+        match index {
+            0  => Some(FreezeTraitLangItem),
+            1  => Some(SendTraitLangItem),
+            2  => Some(SizedTraitLangItem),
+
+            3  => Some(DropTraitLangItem),
+
+            4  => Some(AddTraitLangItem),
+            5  => Some(SubTraitLangItem),
+            6  => Some(MulTraitLangItem),
+            7  => Some(DivTraitLangItem),
+            8  => Some(RemTraitLangItem),
+            9  => Some(NegTraitLangItem),
+            10 => Some(NotTraitLangItem),
+            11 => Some(BitXorTraitLangItem),
+            12 => Some(BitAndTraitLangItem),
+            13 => Some(BitOrTraitLangItem),
+            14 => Some(ShlTraitLangItem),
+            15 => Some(ShrTraitLangItem),
+            16 => Some(IndexTraitLangItem),
+
+            17 => Some(EqTraitLangItem),
+            18 => Some(OrdTraitLangItem),
+
+            19 => Some(StrEqFnLangItem),
+            20 => Some(UniqStrEqFnLangItem),
+            21 => Some(FailFnLangItem),
+            22 => Some(FailBoundsCheckFnLangItem),
+            23 => Some(ExchangeMallocFnLangItem),
+            24 => Some(ClosureExchangeMallocFnLangItem),
+            25 => Some(ExchangeFreeFnLangItem),
+            26 => Some(MallocFnLangItem),
+            27 => Some(FreeFnLangItem),
+            28 => Some(BorrowAsImmFnLangItem),
+            29 => Some(BorrowAsMutFnLangItem),
+            30 => Some(ReturnToMutFnLangItem),
+            31 => Some(CheckNotBorrowedFnLangItem),
+            32 => Some(StrDupUniqFnLangItem),
+            33 => Some(RecordBorrowFnLangItem),
+            34 => Some(UnrecordBorrowFnLangItem),
+
+            35 => Some(StartFnLangItem),
+
+            36 => Some(TyDescStructLangItem),
+            37 => Some(TyVisitorTraitLangItem),
+            38 => Some(OpaqueStructLangItem),
+
+            39 => Some(EventLoopFactoryLangItem),
+
+            40 => Some(DefaultTraitLangItem),
+
+            41 => Some(FnTraitLangItem),
+
+            42 => Some(DropFlagLangItem),
+
+            43 => Some(CopyTraitLangItem),
+
+            44 => Some(IndexMutTraitLangItem),
+
+            45 => Some(AddWithOverflowFnLangItem),
+            46 => Some(SubWithOverflowFnLangItem),
+            47 => Some(MulWithOverflowFnLangItem),
+            _  => None
+        }
+    }
+
+    /// Whether this item is only meaningful in an executable crate.
+    pub fn executable_only(&self) -> bool {
+        match *self {
+            StartFnLangItem => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the compiler copes with this item being missing. These are
+    /// hooks which no crate is expected to define.
+    pub fn is_optional(&self) -> bool {
+        match *self {
+            EventLoopFactoryLangItem | DefaultTraitLangItem | FnTraitLangItem |
+            DropFlagLangItem | CopyTraitLangItem | IndexMutTraitLangItem |
+            AddWithOverflowFnLangItem | SubWithOverflowFnLangItem |
+            MulWithOverflowFnLangItem => true,
+            _ => false,
+        }
+    }
+
+    /// A short description of the item, for documentation and diagnostics.
+    /// Unlike `item_name`, this is not the spelling used in `#[lang]`.
+    pub fn description(&self) -> &'static str {
+        match *self {
+            FreezeTraitLangItem => "the trait for types without interior mutability",
+            SendTraitLangItem => "the trait for types which can be sent between tasks",
+            SizedTraitLangItem => "the trait for types with a size known at compile time",
+
+            DropTraitLangItem => "the trait for types with a destructor",
+
+            AddTraitLangItem => "the trait for the `+` operator",
+            SubTraitLangItem => "the trait for the `-` operator",
+            MulTraitLangItem => "the trait for the `*` operator",
+            DivTraitLangItem => "the trait for the `/` operator",
+            RemTraitLangItem => "the trait for the `%` operator",
+            NegTraitLangItem => "the trait for the unary `-` operator",
+            NotTraitLangItem => "the trait for the unary `!` operator",
+            BitXorTraitLangItem => "the trait for the `^` operator",
+            BitAndTraitLangItem => "the trait for the `&` operator",
+            BitOrTraitLangItem => "the trait for the `|` operator",
+            ShlTraitLangItem => "the trait for the `<<` operator",
+            ShrTraitLangItem => "the trait for the `>>` operator",
+            IndexTraitLangItem => "the trait for the `[]` indexing operator",
+
+            EqTraitLangItem => "the trait for the `==` and `!=` operators",
+            OrdTraitLangItem => "the trait for the `<`, `<=`, `>` and `>=` operators",
+
+            StrEqFnLangItem => "the function comparing strings in patterns",
+            UniqStrEqFnLangItem => "the function comparing owned strings in patterns",
+            FailFnLangItem => "the function called when a task fails",
+            FailBoundsCheckFnLangItem => "the function called when an index is out of bounds",
+            ExchangeMallocFnLangItem => "the function allocating owned boxes",
+            ClosureExchangeMallocFnLangItem => "the function allocating owned closures",
+            ExchangeFreeFnLangItem => "the function freeing owned boxes",
+            MallocFnLangItem => "the function allocating managed boxes",
+            FreeFnLangItem => "the function freeing managed boxes",
+            BorrowAsImmFnLangItem => "the function freezing a managed box for a borrow",
+            BorrowAsMutFnLangItem => "the function borrowing a managed box mutably",
+            ReturnToMutFnLangItem => "the function ending a borrow of a managed box",
+            CheckNotBorrowedFnLangItem =>
+                "the function checking that a managed box is not borrowed",
+            StrDupUniqFnLangItem => "the function copying a string literal into an owned string",
+            RecordBorrowFnLangItem => "the function recording a borrow for debugging",
+            UnrecordBorrowFnLangItem => "the function forgetting a recorded borrow",
+
+            StartFnLangItem => "the function which starts the runtime and calls `main`",
+
+            TyDescStructLangItem => "the type descriptor structure",
+            TyVisitorTraitLangItem => "the trait for visiting types through reflection",
+            OpaqueStructLangItem => "the opaque structure used by reflection",
+
+            EventLoopFactoryLangItem => "the function creating the runtime's event loop",
+
+            DefaultTraitLangItem => "the trait for types with a default value",
+
+            FnTraitLangItem => "the trait for the `()` call operator",
+
+            DropFlagLangItem => "the type of the flag marking a value as dropped",
+
+            CopyTraitLangItem => "the trait for types which are copied implicitly",
+
+            IndexMutTraitLangItem => "the trait for mutable `[]` indexing",
+
+            AddWithOverflowFnLangItem => "the function called when an addition overflows",
+            SubWithOverflowFnLangItem => "the function called when a subtraction overflows",
+            MulWithOverflowFnLangItem => "the function called when a multiplication overflows",
+        }
+    }
+
+    pub fn category(&self) -> LangItemCategory {
+        match *self {
+            FreezeTraitLangItem | SendTraitLangItem |
+            SizedTraitLangItem | CopyTraitLangItem => KindCategory,
+
+            AddTraitLangItem | SubTraitLangItem | MulTraitLangItem |
+            DivTraitLangItem | RemTraitLangItem | NegTraitLangItem |
+            NotTraitLangItem | BitXorTraitLangItem | BitAndTraitLangItem |
+            BitOrTraitLangItem | ShlTraitLangItem | ShrTraitLangItem |
+            IndexTraitLangItem | IndexMutTraitLangItem |
+            FnTraitLangItem => OperatorCategory,
+
+            EqTraitLangItem | OrdTraitLangItem => ComparisonCategory,
+
+            DropTraitLangItem | DefaultTraitLangItem => TraitCategory,
+
+            StrEqFnLangItem | UniqStrEqFnLangItem | FailFnLangItem |
+            FailBoundsCheckFnLangItem | StartFnLangItem |
+            EventLoopFactoryLangItem | DropFlagLangItem |
+            AddWithOverflowFnLangItem | SubWithOverflowFnLangItem |
+            MulWithOverflowFnLangItem => RuntimeCategory,
+
+            ExchangeMallocFnLangItem | ClosureExchangeMallocFnLangItem |
+            ExchangeFreeFnLangItem | MallocFnLangItem | FreeFnLangItem |
+            StrDupUniqFnLangItem => AllocationCategory,
+
+            BorrowAsImmFnLangItem | BorrowAsMutFnLangItem |
+            ReturnToMutFnLangItem | CheckNotBorrowedFnLangItem |
+            RecordBorrowFnLangItem | UnrecordBorrowFnLangItem => BorrowCategory,
+
+            TyDescStructLangItem | TyVisitorTraitLangItem |
+            OpaqueStructLangItem => ReflectionCategory,
+        }
+    }
 }
 
+// The conventional names of the `ord` trait's comparison methods, in the
+// order `ord_methods` returns them.
+static ORD_METHOD_NAMES: [&'static str, ..4] = ["lt", "le", "gt", "ge"];
+
 pub struct LanguageItems {
-    items: [Option<ast::DefId>, ..40]
+    priv items: [Option<ast::DefId>, ..NUM_LANG_ITEMS],
+    priv ord_methods: Option<[ast::DefId, ..4]>,
+    // The reverse of `items`, built by the first `item_for_def_id` and
+    // dropped whenever the table changes.
+    priv by_def_id: RefCell<Option<HashMap<ast::DefId, uint>>>,
 }
 
 impl LanguageItems {
     pub fn new() -> LanguageItems {
         LanguageItems {
-            items: [ None, ..40 ]
+            items: [ None, ..NUM_LANG_ITEMS ],
+            ord_methods: None,
+            by_def_id: RefCell::new(None),
+        }
+    }
+
+    /// A table with every item resolved, to `base` and the nodes following
+    /// it in order, for tests of passes which need the items to exist.
+    #[cfg(test)]
+    pub fn all_stubbed(base: ast::DefId) -> LanguageItems {
+        let mut items = LanguageItems::new();
+        for i in range(0, NUM_LANG_ITEMS) {
+            let id = ast::DefId { crate: base.crate, node: base.node + i as ast::NodeId };
+            items.set(i, Some(id));
+        }
+        items
+    }
+
+    /// Sets the definition of the item at `index`.
+    pub fn set(&mut self, index: uint, id: Option<ast::DefId>) {
+        self.items[index] = id;
+        self.by_def_id.with_mut(|cache| *cache = None);
+    }
+
+    /// Forgets the definition of `item`, e.g. for an item the current
+    /// build mode has no use for. `require` then reports it as missing.
+    pub fn clear(&mut self, item: LangItem) {
+        self.set(item as uint, None);
+    }
+
+    /// Returns the language item defined by `id`, if any.
+    pub fn item_for_def_id(&self, id: ast::DefId) -> Option<LangItem> {
+        let mut cache = self.by_def_id.borrow_mut();
+        if cache.get().is_none() {
+            let mut map = HashMap::new();
+            for (i, item) in self.items() {
+                for &id in item.iter() {
+                    map.insert(id, i);
+                }
+            }
+            *cache.get() = Some(map);
         }
+        let index = cache.get().get_ref().find(&id).map(|&i| i);
+        index.and_then(LangItem::from_uint)
+    }
+
+    /// Returns the name of the language item defined by `id`, if any.
+    pub fn name_for_def_id(&self, id: ast::DefId) -> Option<&'static str> {
+        self.item_for_def_id(id).map(|item| LanguageItems::item_name(item as uint))
     }
 
     pub fn items<'a>(&'a self) -> Enumerate<vec::VecIterator<'a, Option<ast::DefId>>> {
         self.items.iter().enumerate()
     }
 
+    /// Returns a copy of the whole table, to be handed back to `restore`
+    /// after a speculative pass.
+    pub fn snapshot(&self) -> [Option<ast::DefId>, ..NUM_LANG_ITEMS] {
+        self.items
+    }
+
+    /// Resets the table to the state captured by `snapshot`.
+    pub fn restore(&mut self, snap: [Option<ast::DefId>, ..NUM_LANG_ITEMS]) {
+        self.items = snap;
+        self.by_def_id.with_mut(|cache| *cache = None);
+    }
+
+    /// Returns the number of language items which have been resolved.
+    pub fn count_resolved(&self) -> uint {
+        self.items.iter().count(|item| item.is_some())
+    }
+
+    /// Returns true if every language item has been resolved.
+    pub fn is_complete(&self) -> bool {
+        self.items.iter().all(|item| item.is_some())
+    }
+
+    /// Returns true if every language item which is not `is_optional` has
+    /// been resolved.
+    pub fn has_required(&self) -> bool {
+        self.items().all(|(i, item)| {
+            item.is_some() || LangItem::from_uint(i).unwrap().is_optional()
+        })
+    }
+
+    /// Returns the language items which have not been resolved, in index
+    /// order.
+    pub fn missing(&self) -> ~[LangItem] {
+        self.items().filter(|&(_, item)| item.is_none())
+                    .filter_map(|(i, _)| LangItem::from_uint(i))
+                    .collect()
+    }
+
+    /// Returns the language items which have not been resolved, each with
+    /// its `description`, for listing them to the user.
+    pub fn missing_described(&self) -> ~[(LangItem, &'static str)] {
+        self.missing().move_iter().map(|item| (item, item.description())).collect()
+    }
+
     pub fn item_name(index: uint) -> &'static str {
         match index {
             0  => "freeze",
@@ -148,20 +486,70 @@ impl LanguageItems {
 
             39 => "event_loop_factory",
 
+            40 => "default",
+
+            41 => "fn",
+
+            42 => "drop_flag",
+
+            43 => "copy",
+
+            44 => "index_mut",
+
+            45 => "add_with_overflow",
+            46 => "sub_with_overflow",
+            47 => "mul_with_overflow",
+
             _ => "???"
         }
     }
 
+    /// Returns the `#[lang]` names of all the language items, in order.
+    pub fn known_names() -> ~[&'static str] {
+        range(0, NUM_LANG_ITEMS).map(|i| LanguageItems::item_name(i)).collect()
+    }
+
+    /// Returns every resolved language item in the given category, along with
+    /// the item it resolved to.
+    pub fn items_in_category(&self, cat: LangItemCategory)
+                             -> ~[(LangItem, ast::DefId)] {
+        let mut result = ~[];
+        for (i, item) in self.items() {
+            match (LangItem::from_uint(i), *item) {
+                (Some(it), Some(id)) if it.category() == cat => {
+                    result.push((it, id));
+                }
+                _ => {}
+            }
+        }
+        result
+    }
+
     // FIXME #4621: Method macros sure would be nice here.
 
     pub fn require(&self, it: LangItem) -> Result<ast::DefId, ~str> {
-        match self.items[it as uint] {
+        self.require_index(it as uint)
+    }
+
+    // Looks the item up by index rather than trusting the discriminant, so
+    // that an item from a newer `LangItem` than this table was built for is
+    // reported instead of indexing past the end.
+    fn require_index(&self, index: uint) -> Result<ast::DefId, ~str> {
+        if index >= self.items.len() {
+            return Err(format!("lang item \#{} is out of range (only {} are known)",
+                               index, self.items.len()));
+        }
+        match self.items[index] {
             Some(id) => Ok(id),
             None => Err(format!("requires `{}` lang_item",
-                             LanguageItems::item_name(it as uint)))
+                             LanguageItems::item_name(index)))
         }
     }
 
+    // `copy` is deliberately not mapped here: there is no `BuiltinBound`
+    // for it, and whether a type is implicitly copied is still decided from
+    // its contents by `ty::type_moves_by_default`. The lang item only names
+    // the trait so that diagnostics can refer to it.
     pub fn to_builtin_kind(&self, id: ast::DefId) -> Option<BuiltinBound> {
         if Some(id) == self.freeze_trait() {
             Some(BoundFreeze)
@@ -234,6 +622,11 @@ impl LanguageItems {
     pub fn ord_trait(&self) -> Option<ast::DefId> {
         self.items[OrdTraitLangItem as uint]
     }
+    /// The `lt`, `le`, `gt` and `ge` methods of the `ord` trait, in that
+    /// order, or `None` if the trait is missing or lacks any of them.
+    pub fn ord_methods(&self) -> Option<[ast::DefId, ..4]> {
+        self.ord_methods
+    }
 
     pub fn str_eq_fn(&self) -> Option<ast::DefId> {
         self.items[StrEqFnLangItem as uint]
@@ -298,14 +691,80 @@ impl LanguageItems {
     pub fn event_loop_factory(&self) -> Option<ast::DefId> {
         self.items[EventLoopFactoryLangItem as uint]
     }
+
+    pub fn default_trait(&self) -> Option<ast::DefId> {
+        self.items[DefaultTraitLangItem as uint]
+    }
+
+    pub fn fn_trait(&self) -> Option<ast::DefId> {
+        self.items[FnTraitLangItem as uint]
+    }
+
+    pub fn drop_flag(&self) -> Option<ast::DefId> {
+        self.items[DropFlagLangItem as uint]
+    }
+
+    pub fn copy_trait(&self) -> Option<ast::DefId> {
+        self.items[CopyTraitLangItem as uint]
+    }
+
+    pub fn index_mut_trait(&self) -> Option<ast::DefId> {
+        self.items[IndexMutTraitLangItem as uint]
+    }
+
+    // The runtime hooks of overflow-trapping builds. Only crates built that
+    // way need to define them.
+    pub fn add_with_overflow_fn(&self) -> Option<ast::DefId> {
+        self.items[AddWithOverflowFnLangItem as uint]
+    }
+    pub fn sub_with_overflow_fn(&self) -> Option<ast::DefId> {
+        self.items[SubWithOverflowFnLangItem as uint]
+    }
+    pub fn mul_with_overflow_fn(&self) -> Option<ast::DefId> {
+        self.items[MulWithOverflowFnLangItem as uint]
+    }
+}
+
+/// The outcome of collecting the language items of a crate.
+pub struct CollectionReport {
+    /// The items that were found.
+    items: LanguageItems,
+    /// The errors found while collecting, such as duplicate definitions.
+    errors: ~[~str],
+    /// The warnings found while collecting, with the span of the item each
+    /// one is about.
+    warnings: ~[(Span, ~str)],
+}
+
+impl CollectionReport {
+    /// Reports the errors and warnings on `session`.
+    pub fn emit(&self, session: Session) {
+        for msg in self.errors.iter() {
+            session.err(msg.as_slice());
+        }
+        for &(span, ref msg) in self.warnings.iter() {
+            session.span_warn(span, msg.as_slice());
+        }
+    }
 }
 
 struct LanguageItemCollector {
     items: LanguageItems,
 
+    // Problems found so far, reported on the session by
+    // `CollectionReport::emit` rather than as they are found.
+    errors: ~[~str],
+    warnings: ~[(Span, ~str)],
+
     session: Session,
 
     item_refs: HashMap<&'static str, uint>,
+
+    // When set, the local crate stops being searched once every item which
+    // is not optional has been found. Waiting for every slot instead would
+    // never stop early, as no crate defines the optional hooks. Duplicate
+    // definitions and optional items after that point go unrecorded.
+    stop_when_complete: bool,
 }
 
 struct LanguageItemVisitor<'self> {
@@ -314,13 +773,34 @@ struct LanguageItemVisitor<'self> {
 
 impl<'self> Visitor<()> for LanguageItemVisitor<'self> {
     fn visit_item(&mut self, item: @ast::item, _: ()) {
+        if self.this.stop_when_complete && self.this.items.has_required() {
+            return;
+        }
+
         match extract(item.attrs) {
             Some(value) => {
                 let item_index = self.this.item_refs.find_equiv(&value).map(|x| *x);
 
                 match item_index {
                     Some(item_index) => {
-                        self.this.collect_item(item_index, local_def(item.id))
+                        self.this.collect_item(item_index, local_def(item.id));
+                        self.this.check_crate_type(item_index, item.span);
+
+                        match item.node {
+                            ast::item_trait(_, _, ref methods)
+                                    if item_index == OrdTraitLangItem as uint => {
+                                let session = self.this.session;
+                                let methods = methods.map(|method| {
+                                    let (ident, id) = match *method {
+                                        ast::required(ref m) => (m.ident, m.id),
+                                        ast::provided(m) => (m.ident, m.id),
+                                    };
+                                    (session.str_of(ident), local_def(id))
+                                });
+                                self.this.collect_ord_methods(methods);
+                            }
+                            _ => {}
+                        }
                     }
                     None => {}
                 }
@@ -333,7 +813,15 @@ impl<'self> Visitor<()> for LanguageItemVisitor<'self> {
 }
 
 impl LanguageItemCollector {
-    pub fn new(session: Session) -> LanguageItemCollector {
+    /// Creates a collector that recognizes the built-in lang item names,
+    /// plus the names in `aliases`. An alias with the same name as a
+    /// built-in one takes its place.
+    ///
+    /// With `stop_when_complete`, the local crate is only searched until
+    /// every item which is not `is_optional` has been found. Duplicate
+    /// definitions and optional items after that point are not recorded.
+    pub fn new(session: Session, aliases: &[(&'static str, LangItem)],
+               stop_when_complete: bool) -> LanguageItemCollector {
         let mut item_refs = HashMap::new();
 
         item_refs.insert("freeze", FreezeTraitLangItem as uint);
@@ -383,19 +871,49 @@ impl LanguageItemCollector {
         item_refs.insert("opaque", OpaqueStructLangItem as uint);
         item_refs.insert("event_loop_factory", EventLoopFactoryLangItem as uint);
 
+        item_refs.insert("default", DefaultTraitLangItem as uint);
+
+        item_refs.insert("fn", FnTraitLangItem as uint);
+
+        item_refs.insert("drop_flag", DropFlagLangItem as uint);
+
+        item_refs.insert("copy", CopyTraitLangItem as uint);
+
+        item_refs.insert("index_mut", IndexMutTraitLangItem as uint);
+
+        item_refs.insert("add_with_overflow", AddWithOverflowFnLangItem as uint);
+        item_refs.insert("sub_with_overflow", SubWithOverflowFnLangItem as uint);
+        item_refs.insert("mul_with_overflow", MulWithOverflowFnLangItem as uint);
+
+        for &(name, item) in aliases.iter() {
+            item_refs.insert(name, item as uint);
+        }
+
         LanguageItemCollector {
             session: session,
             items: LanguageItems::new(),
-            item_refs: item_refs
+            errors: ~[],
+            warnings: ~[],
+            item_refs: item_refs,
+            stop_when_complete: stop_when_complete,
         }
     }
 
     pub fn collect_item(&mut self, item_index: uint, item_def_id: ast::DefId) {
+        // An index read from the metadata of a crate built by another
+        // version of the compiler may name an item this one doesn't know.
+        if item_index >= NUM_LANG_ITEMS {
+            self.errors.push(format!("lang item \#{} of crate {} is out of range \
+                                      (only {} are known)",
+                                     item_index, item_def_id.crate, NUM_LANG_ITEMS));
+            return;
+        }
+
         // Check for duplicates.
         match self.items.items[item_index] {
             Some(original_def_id) if original_def_id != item_def_id => {
-                self.session.err(format!("duplicate entry for `{}`",
-                                      LanguageItems::item_name(item_index)));
+                self.errors.push(format!("duplicate entry for `{}`",
+                                         LanguageItems::item_name(item_index)));
             }
             Some(_) | None => {
                 // OK.
@@ -403,7 +921,38 @@ impl LanguageItemCollector {
         }
 
         // Matched.
-        self.items.items[item_index] = Some(item_def_id);
+        self.items.set(item_index, Some(item_def_id));
+    }
+
+    // Warns, under -Z lang-item-crate-type, about local items which the
+    // type of crate being built has no use for.
+    fn check_crate_type(&mut self, item_index: uint, span: Span) {
+        if !self.session.debugging_opt(session::lang_item_crate_type) {
+            return;
+        }
+        let item = LangItem::from_uint(item_index).unwrap();
+        if item.executable_only() && *self.session.building_library {
+            self.warnings.push((span,
+                format!("lang item `{}` is only used by executables, \
+                         but this crate is a library",
+                        LanguageItems::item_name(item_index))));
+        }
+    }
+
+    // Records the comparison methods of the `ord` trait, given the names
+    // and ids of all of its methods.
+    fn collect_ord_methods(&mut self, methods: &[(@str, ast::DefId)]) {
+        let mut found = [ast::DefId { crate: 0, node: 0 }, ..4];
+        for (i, name) in ORD_METHOD_NAMES.iter().enumerate() {
+            match methods.iter().find(|&&(n, _)| n.as_slice() == *name) {
+                Some(&(_, def_id)) => found[i] = def_id,
+                None => {
+                    self.items.ord_methods = None;
+                    return;
+                }
+            }
+        }
+        self.items.ord_methods = Some(found);
     }
 
     pub fn collect_local_language_items(&mut self, crate: &ast::Crate) {
@@ -417,6 +966,17 @@ impl LanguageItemCollector {
             each_lang_item(crate_store, crate_number, |node_id, item_index| {
                 let def_id = ast::DefId { crate: crate_number, node: node_id };
                 self.collect_item(item_index, def_id);
+
+                if item_index == OrdTraitLangItem as uint {
+                    let method_ids = get_trait_method_def_ids(crate_store, def_id);
+                    let methods = method_ids.map(|&method_id| {
+                        let (ident, _) =
+                            get_method_name_and_explicit_self(crate_store,
+                                                              method_id);
+                        (self.session.str_of(ident), method_id)
+                    });
+                    self.collect_ord_methods(methods);
+                }
                 true
             });
         })
@@ -426,6 +986,12 @@ impl LanguageItemCollector {
         self.collect_local_language_items(crate);
         self.collect_external_language_items();
     }
+
+    /// Ends the collection, returning what was found.
+    pub fn finish(self) -> CollectionReport {
+        let LanguageItemCollector { items, errors, warnings, _ } = self;
+        CollectionReport { items: items, errors: errors, warnings: warnings }
+    }
 }
 
 pub fn extract(attrs: &[ast::Attribute]) -> Option<@str> {
@@ -444,9 +1010,571 @@ pub fn extract(attrs: &[ast::Attribute]) -> Option<@str> {
 pub fn collect_language_items(crate: &ast::Crate,
                               session: Session)
                            -> LanguageItems {
-    let mut collector = LanguageItemCollector::new(session);
+    let mut collector = LanguageItemCollector::new(session, [], false);
     collector.collect(crate);
-    let LanguageItemCollector { items, _ } = collector;
+    let report = collector.finish();
+    report.emit(session);
     session.abort_if_errors();
+    let CollectionReport { items, _ } = report;
+    match freestanding_error(crate, session, &items) {
+        Some(msg) => session.err(msg),
+        None => {}
+    }
     items
 }
+
+/// Checks for a `#[no_std]` executable in which no language item was found
+/// at all, which almost always means the crate defining them is not linked.
+///
+/// Libraries are not checked, since a library with no lang items of its own
+/// is fine, and neither are executables with their own `#[start]` function,
+/// which can do without any. The error does not abort compilation, so that
+/// the missing items are still reported one by one where they are needed.
+pub fn freestanding_error(crate: &ast::Crate, session: Session,
+                          items: &LanguageItems) -> Option<~str> {
+    if !attr::contains_name(crate.attrs, "no_std") || *session.building_library ||
+            items.count_resolved() != 0 {
+        return None;
+    }
+    if crate.module.items.iter().any(|item| attr::contains_name(item.attrs, "start")) {
+        return None;
+    }
+    Some(~"no language items found; are you missing the core library?")
+}
+
+/// Collects the language items of `crate`, then checks that each of
+/// `required` was found.
+///
+/// Unlike `collect_language_items` this does not abort on errors found while
+/// collecting; they stay on the session. A missing required item is
+/// returned as an error for the caller to report.
+pub fn collect_and_require(crate: &ast::Crate,
+                           session: Session,
+                           required: &[LangItem])
+                        -> Result<LanguageItems, ~str> {
+    let mut collector = LanguageItemCollector::new(session, [], false);
+    collector.collect(crate);
+    let report = collector.finish();
+    report.emit(session);
+    let CollectionReport { items, _ } = report;
+    for &it in required.iter() {
+        match items.require(it) {
+            Ok(_) => {}
+            Err(msg) => return Err(msg)
+        }
+    }
+    Ok(items)
+}
+
+#[cfg(test)]
+mod test {
+    use driver::driver::{build_session, build_session_options, optgroups};
+    use driver::session::Session;
+    use front::assign_node_ids::assign_node_ids;
+    use middle::lang_items::{LanguageItemCollector, LanguageItems, collect_and_require};
+    use middle::lang_items::{CollectionReport, freestanding_error};
+    use middle::lang_items::{DefaultTraitLangItem, DropTraitLangItem};
+    use middle::lang_items::{FnTraitLangItem, IndexTraitLangItem};
+    use middle::lang_items::{DropFlagLangItem, StartFnLangItem};
+    use middle::lang_items::{FreezeTraitLangItem, SendTraitLangItem};
+    use middle::lang_items::{LangItem, MallocFnLangItem, FreeFnLangItem, AddTraitLangItem};
+    use middle::lang_items::{NUM_LANG_ITEMS, RecordBorrowFnLangItem};
+    use middle::lang_items::{AllocationCategory, OperatorCategory, TraitCategory};
+    use middle::lang_items::{RuntimeCategory, KindCategory, CopyTraitLangItem};
+    use middle::lang_items::IndexMutTraitLangItem;
+    use middle::lang_items::{AddWithOverflowFnLangItem, MulWithOverflowFnLangItem};
+
+    use extra::getopts::groups::getopts;
+    use syntax::ast;
+    use syntax::codemap::Span;
+    use syntax::codemap;
+    use syntax::diagnostic;
+    use syntax::parse;
+
+    fn test_session() -> Session {
+        test_session_with([], @diagnostic::DefaultEmitter as @diagnostic::Emitter)
+    }
+
+    fn test_session_with(args: &[~str], emitter: @diagnostic::Emitter) -> Session {
+        let matches = &match getopts(args, optgroups()) {
+            Ok(m) => m,
+            Err(f) => fail!("test_session: {}", f.to_err_msg())
+        };
+        let sessopts = build_session_options(@"rustc", matches, emitter);
+        build_session(sessopts, emitter)
+    }
+
+    // Collects the lang items defined locally in `source`.
+    fn collect_source(source: &str) -> LanguageItems {
+        collect_source_with(test_session(), source)
+    }
+
+    fn collect_source_with(sess: Session, source: &str) -> LanguageItems {
+        let crate = parse::parse_crate_from_source_str(@"<test>",
+                                                       source.to_managed(),
+                                                       ~[],
+                                                       sess.parse_sess);
+        let crate = assign_node_ids(sess, crate);
+        let mut collector = LanguageItemCollector::new(sess, [], false);
+        collector.collect_local_language_items(&crate);
+        let report = collector.finish();
+        report.emit(sess);
+        let CollectionReport { items, _ } = report;
+        items
+    }
+
+    #[test]
+    fn test_collection_report() {
+        let sess = test_session();
+        let crate = parse::parse_crate_from_source_str(@"<test>",
+                                                       @"#[lang=\"drop\"] trait Drop {}\n\
+                                                         #[lang=\"drop\"] trait Drop2 {}",
+                                                       ~[],
+                                                       sess.parse_sess);
+        let crate = assign_node_ids(sess, crate);
+        let mut collector = LanguageItemCollector::new(sess, [], false);
+        collector.collect_local_language_items(&crate);
+        let report = collector.finish();
+
+        assert_eq!(report.errors, ~[~"duplicate entry for `drop`"]);
+        assert!(report.warnings.is_empty());
+        assert!(report.items.drop_trait().is_some());
+        // Nothing reaches the session until the report is emitted.
+        assert_eq!(sess.err_count(), 0);
+    }
+
+    #[test]
+    fn test_default_trait() {
+        let items = collect_source("#[lang=\"default\"] trait Default {}");
+        assert!(items.default_trait().is_some());
+        assert!(items.drop_trait().is_none());
+        assert_eq!(LanguageItems::item_name(DefaultTraitLangItem as uint),
+                   "default");
+        assert!(DefaultTraitLangItem.category() == TraitCategory);
+        assert!(DropTraitLangItem.category() == TraitCategory);
+    }
+
+    #[test]
+    fn test_fn_trait() {
+        let items = collect_source("#[lang=\"fn\"] trait Fn {}");
+        assert!(items.fn_trait().is_some());
+        assert!(items.index_trait().is_none());
+        assert_eq!(LanguageItems::item_name(FnTraitLangItem as uint), "fn");
+        assert!(FnTraitLangItem.category() == OperatorCategory);
+        assert!(IndexTraitLangItem.category() == OperatorCategory);
+    }
+
+    #[test]
+    fn test_drop_flag() {
+        assert!(collect_source("").drop_flag().is_none());
+
+        let items = collect_source("#[lang=\"drop_flag\"] struct DropFlag;");
+        assert!(items.drop_flag().is_some());
+        assert_eq!(LanguageItems::item_name(DropFlagLangItem as uint), "drop_flag");
+        assert!(DropFlagLangItem.category() == RuntimeCategory);
+    }
+
+    #[test]
+    fn test_copy_trait() {
+        let items = collect_source("#[lang=\"copy\"] trait Copy {}");
+        let copy = items.copy_trait();
+        assert!(copy.is_some());
+        assert!(items.to_builtin_kind(copy.unwrap()).is_none());
+        assert_eq!(LanguageItems::item_name(CopyTraitLangItem as uint), "copy");
+        assert!(CopyTraitLangItem.category() == KindCategory);
+    }
+
+    #[test]
+    fn test_overflow_fns() {
+        let items = collect_source("#[lang=\"add_with_overflow\"]\n\
+                                    fn add_with_overflow(a: int, b: int) -> int { a + b }");
+        assert!(items.add_with_overflow_fn().is_some());
+        assert!(items.sub_with_overflow_fn().is_none());
+        assert!(items.mul_with_overflow_fn().is_none());
+        assert_eq!(LanguageItems::item_name(MulWithOverflowFnLangItem as uint),
+                   "mul_with_overflow");
+        assert!(AddWithOverflowFnLangItem.category() == RuntimeCategory);
+
+        assert!(collect_source("").add_with_overflow_fn().is_none());
+    }
+
+    #[test]
+    fn test_freestanding_error() {
+        let check = |source: &str, building_library: bool| -> Option<~str> {
+            let sess = test_session();
+            *sess.building_library = building_library;
+            let crate = parse::parse_crate_from_source_str(@"<test>",
+                                                           source.to_managed(),
+                                                           ~[],
+                                                           sess.parse_sess);
+            let items = collect_source_with(sess, source);
+            freestanding_error(&crate, sess, &items)
+        };
+
+        assert_eq!(check("#[no_std]; fn main() {}", false),
+                   Some(~"no language items found; are you missing the core library?"));
+        assert_eq!(check("#[no_std]; fn main() {}", true), None);
+        assert_eq!(check("#[no_std]; #[start] fn start(_: int, _: **u8) -> int { 0 }", false),
+                   None);
+        assert_eq!(check("#[no_std]; #[lang=\"drop\"] trait Drop {}", false), None);
+        assert_eq!(check("fn main() {}", false), None);
+    }
+
+    #[test]
+    fn test_index_mut_trait() {
+        let items = collect_source("#[lang=\"index\"] trait Index {}\n\
+                                    #[lang=\"index_mut\"] trait IndexMut {}");
+        let index = items.index_trait();
+        let index_mut = items.index_mut_trait();
+        assert!(index.is_some());
+        assert!(index_mut.is_some());
+        assert!(index != index_mut);
+        assert_eq!(LanguageItems::item_name(IndexMutTraitLangItem as uint), "index_mut");
+        assert!(IndexMutTraitLangItem.category() == OperatorCategory);
+
+        assert!(collect_source("#[lang=\"index\"] trait Index {}").index_mut_trait().is_none());
+    }
+
+    #[test]
+    fn test_collect_and_require() {
+        let sess = test_session();
+        let crate = parse::parse_crate_from_source_str(@"<test>",
+                                                       @"#[lang=\"drop\"] trait Drop {}",
+                                                       ~[],
+                                                       sess.parse_sess);
+        let crate = assign_node_ids(sess, crate);
+
+        let items = collect_and_require(&crate, sess, [DropTraitLangItem]).unwrap();
+        assert!(items.drop_trait().is_some());
+
+        let err = collect_and_require(&crate, sess, [DropTraitLangItem, StartFnLangItem]);
+        assert_eq!(err.unwrap_err(), ~"requires `start` lang_item");
+    }
+
+    #[test]
+    fn test_require_out_of_range() {
+        let items = collect_source("#[lang=\"drop\"] trait Drop {}");
+        assert!(items.require(DropTraitLangItem).is_ok());
+        assert_eq!(items.require_index(NUM_LANG_ITEMS).unwrap_err(),
+                   format!("lang item \#{} is out of range (only {} are known)",
+                           NUM_LANG_ITEMS, NUM_LANG_ITEMS));
+    }
+
+    #[test]
+    fn test_aliases() {
+        let source = "#[lang=\"frozen\"] trait Freeze {}\n\
+                      #[lang=\"drop\"] trait Drop {}\n\
+                      #[lang=\"sized\"] trait Send {}";
+        let sess = test_session();
+        let crate = parse::parse_crate_from_source_str(@"<test>",
+                                                       source.to_managed(),
+                                                       ~[],
+                                                       sess.parse_sess);
+        let crate = assign_node_ids(sess, crate);
+        let aliases = [("frozen", FreezeTraitLangItem), ("sized", SendTraitLangItem)];
+        let mut collector = LanguageItemCollector::new(sess, aliases, false);
+        collector.collect_local_language_items(&crate);
+
+        // `frozen` is added, `drop` still works, and `sized` now means `Send`.
+        assert!(collector.items.freeze_trait().is_some());
+        assert!(collector.items.drop_trait().is_some());
+        assert!(collector.items.send_trait().is_some());
+        assert!(collector.items.sized_trait().is_none());
+    }
+
+    #[test]
+    fn test_from_uint() {
+        for i in range(0, NUM_LANG_ITEMS) {
+            let it = LangItem::from_uint(i).unwrap();
+            assert_eq!(it as uint, i);
+        }
+        assert!(LangItem::from_uint(NUM_LANG_ITEMS).is_none());
+    }
+
+    // The lang items as of the last review. Update this together with the
+    // table when adding or removing an item.
+    static KNOWN_NAMES_BASELINE: &'static [&'static str] = &[
+        "freeze", "send", "sized",
+        "drop",
+        "add", "sub", "mul", "div", "rem", "neg", "not", "bitxor", "bitand",
+        "bitor", "shl", "shr", "index",
+        "eq", "ord",
+        "str_eq", "uniq_str_eq", "fail_", "fail_bounds_check", "exchange_malloc",
+        "closure_exchange_malloc", "exchange_free", "malloc", "free",
+        "borrow_as_imm", "borrow_as_mut", "return_to_mut", "check_not_borrowed",
+        "strdup_uniq", "record_borrow", "unrecord_borrow",
+        "start",
+        "ty_desc", "ty_visitor", "opaque",
+        "event_loop_factory",
+        "default",
+        "fn",
+        "drop_flag",
+        "copy",
+        "index_mut",
+        "add_with_overflow", "sub_with_overflow", "mul_with_overflow",
+    ];
+
+    #[test]
+    fn test_known_names_baseline() {
+        let known = LanguageItems::known_names();
+        let mut diff = ~[];
+        for name in known.iter() {
+            if !KNOWN_NAMES_BASELINE.contains(name) {
+                diff.push(format!("+ {}", *name));
+            }
+        }
+        for name in KNOWN_NAMES_BASELINE.iter() {
+            if !known.contains(name) {
+                diff.push(format!("- {}", *name));
+            }
+        }
+        if diff.is_empty() && known.as_slice() != KNOWN_NAMES_BASELINE {
+            diff.push(~"(same items, different order)");
+        }
+        if !diff.is_empty() {
+            fail!("lang items differ from the baseline:\n{}", diff.connect("\n"));
+        }
+    }
+
+    #[test]
+    fn test_description() {
+        for i in range(0, NUM_LANG_ITEMS) {
+            let it = LangItem::from_uint(i).unwrap();
+            assert!(!it.description().is_empty());
+            assert!(it.description() != LanguageItems::item_name(i));
+        }
+        assert_eq!(AddTraitLangItem.description(), "the trait for the `+` operator");
+    }
+
+    #[test]
+    fn test_item_refs_match_item_name() {
+        let collector = LanguageItemCollector::new(test_session(), [], false);
+        for (&name, &index) in collector.item_refs.iter() {
+            assert_eq!(LanguageItems::item_name(index), name);
+        }
+        for i in range(0, NUM_LANG_ITEMS) {
+            let name = LanguageItems::item_name(i);
+            assert_eq!(collector.item_refs.find_equiv(&name).map(|x| *x), Some(i));
+        }
+        assert_eq!(collector.item_refs.len(), NUM_LANG_ITEMS);
+    }
+
+    #[test]
+    fn test_items_in_category() {
+        let items = collect_source("#[lang=\"malloc\"] fn malloc() {}\n\
+                                    #[lang=\"free\"] fn free() {}\n\
+                                    #[lang=\"drop\"] trait Drop {}");
+        let allocs = items.items_in_category(AllocationCategory);
+        assert_eq!(allocs.len(), 2);
+        assert!(allocs.iter().any(|&(it, id)| {
+            it == MallocFnLangItem && Some(id) == items.malloc_fn()
+        }));
+        assert!(allocs.iter().any(|&(it, id)| {
+            it == FreeFnLangItem && Some(id) == items.free_fn()
+        }));
+
+        let traits = items.items_in_category(TraitCategory);
+        assert_eq!(traits.len(), 1);
+    }
+
+    #[test]
+    fn test_stop_when_complete() {
+        // Every item a real crate defines, followed by a duplicate and an
+        // optional item, which are only seen if the search carries on.
+        let mut source = ~"";
+        for i in range(0, NUM_LANG_ITEMS) {
+            if !LangItem::from_uint(i).unwrap().is_optional() {
+                source.push_str(format!("#[lang=\"{}\"] struct S{};\n",
+                                        LanguageItems::item_name(i), i));
+            }
+        }
+        source.push_str("#[lang=\"drop\"] struct Dup; #[lang=\"default\"] struct D;");
+
+        let collect = |stop_when_complete: bool| -> (bool, uint, bool) {
+            let sess = test_session();
+            let crate = parse::parse_crate_from_source_str(@"<test>",
+                                                           source.to_managed(),
+                                                           ~[],
+                                                           sess.parse_sess);
+            let mut collector = LanguageItemCollector::new(sess, [], stop_when_complete);
+            collector.collect_local_language_items(&crate);
+            (collector.items.has_required(), collector.errors.len(),
+             collector.items.default_trait().is_some())
+        };
+
+        assert_eq!(collect(false), (true, 1, true));
+        assert_eq!(collect(true), (true, 0, false));
+    }
+
+    #[test]
+    fn test_collect_item_out_of_range() {
+        let mut collector = LanguageItemCollector::new(test_session(), [], false);
+        let def_id = ast::DefId { crate: 2, node: 7 };
+        collector.collect_item(NUM_LANG_ITEMS, def_id);
+        assert_eq!(collector.items.count_resolved(), 0);
+        assert_eq!(collector.errors,
+                   ~[format!("lang item \#{} of crate 2 is out of range (only {} are known)",
+                             NUM_LANG_ITEMS, NUM_LANG_ITEMS)]);
+    }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let mut items = collect_source("#[lang=\"drop\"] trait Drop {}");
+        let drop = items.drop_trait();
+        let snap = items.snapshot();
+
+        let fake = ast::DefId { crate: 1, node: 2 };
+        items.set(DefaultTraitLangItem as uint, Some(fake));
+        items.clear(DropTraitLangItem);
+        assert_eq!(items.default_trait(), Some(fake));
+        assert_eq!(items.item_for_def_id(fake), Some(DefaultTraitLangItem));
+
+        items.restore(snap);
+        assert!(items.default_trait().is_none());
+        assert!(items.item_for_def_id(fake).is_none());
+        assert_eq!(items.drop_trait(), drop);
+        assert!(drop.is_some());
+    }
+
+    #[test]
+    fn test_all_stubbed() {
+        let base = ast::DefId { crate: 3, node: 100 };
+        let items = LanguageItems::all_stubbed(base);
+        assert_eq!(items.count_resolved(), NUM_LANG_ITEMS);
+        assert!(items.is_complete());
+        assert_eq!(items.freeze_trait(), Some(base));
+        assert_eq!(items.item_for_def_id(ast::DefId { crate: 3, node: 101 }),
+                   Some(SendTraitLangItem));
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut items = collect_source("#[lang=\"record_borrow\"] fn record_borrow() {}\n\
+                                        #[lang=\"drop\"] trait Drop {}");
+        let id = items.record_borrow_fn().unwrap();
+        assert_eq!(items.item_for_def_id(id), Some(RecordBorrowFnLangItem));
+
+        items.clear(RecordBorrowFnLangItem);
+        assert!(items.record_borrow_fn().is_none());
+        assert_eq!(items.require(RecordBorrowFnLangItem).unwrap_err(),
+                   ~"requires `record_borrow` lang_item");
+        assert_eq!(items.item_for_def_id(id), None);
+        assert!(items.drop_trait().is_some());
+    }
+
+    #[test]
+    fn test_item_for_def_id() {
+        let mut items = collect_source("#[lang=\"drop\"] trait Drop {}\n\
+                                        #[lang=\"default\"] trait Default {}");
+        let drop = items.drop_trait().unwrap();
+        let default = items.default_trait().unwrap();
+        assert_eq!(items.item_for_def_id(drop), Some(DropTraitLangItem));
+        assert_eq!(items.item_for_def_id(default), Some(DefaultTraitLangItem));
+
+        let fake = ast::DefId { crate: 1, node: 2 };
+        assert_eq!(items.item_for_def_id(fake), None);
+
+        // Changing the table drops the cached reverse index.
+        items.set(DropTraitLangItem as uint, Some(fake));
+        assert_eq!(items.item_for_def_id(fake), Some(DropTraitLangItem));
+        assert_eq!(items.item_for_def_id(drop), None);
+        assert_eq!(items.item_for_def_id(default), Some(DefaultTraitLangItem));
+    }
+
+    #[test]
+    fn test_name_for_def_id() {
+        let items = collect_source("#[lang=\"drop\"] trait Drop {}");
+        let drop = items.drop_trait().unwrap();
+        assert_eq!(items.name_for_def_id(drop), Some("drop"));
+        assert_eq!(items.name_for_def_id(ast::DefId { crate: 1, node: 2 }), None);
+    }
+
+    #[test]
+    fn test_ord_methods() {
+        let items = collect_source("#[lang=\"ord\"]\n\
+                                    trait Ord {\n\
+                                        fn ge(&self, other: &Self) -> bool;\n\
+                                        fn lt(&self, other: &Self) -> bool;\n\
+                                        fn gt(&self, other: &Self) -> bool;\n\
+                                        fn le(&self, other: &Self) -> bool { true }\n\
+                                    }");
+        let methods = items.ord_methods().unwrap();
+        let (lt, le, gt, ge) = (methods[0], methods[1], methods[2], methods[3]);
+        // Methods get node ids in declaration order: ge, lt, gt, le.
+        assert!(ge.node < lt.node && lt.node < gt.node && gt.node < le.node);
+
+        let items = collect_source("#[lang=\"ord\"]\n\
+                                    trait Ord {\n\
+                                        fn lt(&self, other: &Self) -> bool;\n\
+                                    }");
+        assert!(items.ord_trait().is_some());
+        assert!(items.ord_methods().is_none());
+    }
+
+    // Records the warnings it is given.
+    struct WarningRecorder {
+        warnings: @mut ~[~str],
+    }
+
+    impl diagnostic::Emitter for WarningRecorder {
+        fn emit(&self,
+                _cmsp: Option<(@codemap::CodeMap, Span)>,
+                msg: &str,
+                lvl: diagnostic::level) {
+            if lvl == diagnostic::warning {
+                self.warnings.push(msg.to_owned());
+            }
+        }
+    }
+
+    #[test]
+    fn test_check_crate_type() {
+        let source = "#[lang=\"start\"] fn start() {}\n\
+                      #[lang=\"malloc\"] fn malloc() {}";
+        let warnings_for = |args: &[~str], building_library: bool| -> ~[~str] {
+            let warnings = @mut ~[];
+            let emitter = @WarningRecorder { warnings: warnings };
+            let sess = test_session_with(args, emitter as @diagnostic::Emitter);
+            *sess.building_library = building_library;
+            collect_source_with(sess, source);
+            (*warnings).clone()
+        };
+
+        let flag = [~"-Z", ~"lang-item-crate-type"];
+        assert_eq!(warnings_for(flag, true),
+                   ~[~"lang item `start` is only used by executables, \
+                       but this crate is a library"]);
+        assert!(warnings_for(flag, false).is_empty());
+        assert!(warnings_for([], true).is_empty());
+    }
+
+    #[test]
+    fn test_missing_described() {
+        assert_eq!(LanguageItems::new().missing().len(), NUM_LANG_ITEMS);
+
+        let items = LanguageItems::all_stubbed(ast::DefId { crate: 0, node: 0 });
+        assert!(items.missing_described().is_empty());
+
+        let mut items = items;
+        items.clear(DropTraitLangItem);
+        items.clear(FreeFnLangItem);
+        assert_eq!(items.missing(), ~[DropTraitLangItem, FreeFnLangItem]);
+        assert_eq!(items.missing_described(),
+                   ~[(DropTraitLangItem, DropTraitLangItem.description()),
+                     (FreeFnLangItem, FreeFnLangItem.description())]);
+        for &(_, desc) in items.missing_described().iter() {
+            assert!(!desc.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_count_resolved() {
+        assert_eq!(LanguageItems::new().count_resolved(), 0);
+
+        let items = collect_source("#[lang=\"drop\"] trait Drop {}\n\
+                                    #[lang=\"malloc\"] fn malloc() {}\n\
+                                    #[lang=\"free\"] fn free() {}\n\
+                                    fn other() {}");
+        assert_eq!(items.count_resolved(), 3);
+    }
+}
@@ -37,6 +37,7 @@ use driver::session;
 use middle::privacy;
 use middle::trans::adt; // for `adt::is_ffi_safe`
 use middle::ty;
+use middle::typeck;
 use middle::pat_util;
 use metadata::csearch;
 use util::ppaux::{ty_to_str};
@@ -68,6 +69,7 @@ pub enum lint {
     unnecessary_qualification,
     while_true,
     path_statement,
+    unused_must_use,
     unrecognized_lint,
     non_camel_case_types,
     non_uppercase_statics,
@@ -167,6 +169,13 @@ static lint_table: &'static [(&'static str, LintSpec)] = &[
         default: warn
      }),
 
+    ("unused_must_use",
+     LintSpec {
+        lint: unused_must_use,
+        desc: "unused result of a type or function marked `#[must_use]`",
+        default: allow
+     }),
+
     ("unrecognized_lint",
      LintSpec {
         lint: unrecognized_lint,
@@ -342,6 +351,9 @@ struct Context<'self> {
     cur: SmallIntMap<(level, LintSource)>,
     // context we're checking in (used to access fields like sess)
     tcx: ty::ctxt,
+    // The methods each method call resolved to; used by the unused_must_use
+    // lint.
+    method_map: typeck::method_map,
     // Items exported by the crate; used by the missing_doc lint.
     exported_items: &'self privacy::ExportedItems,
     // The id of the current `ast::struct_def` being walked.
@@ -817,11 +829,12 @@ static other_attrs: &'static [&'static str] = &[
     // item-level
     "address_insignificant", // can be crate-level too
     "thread_local", // for statics
+    "global_allocator", // for statics
     "allow", "deny", "forbid", "warn", // lint options
     "deprecated", "experimental", "unstable", "stable", "locked", "frozen", //item stability
     "crate_map", "cfg", "doc", "export_name", "link_section", "no_freeze",
     "no_mangle", "no_send", "static_assert", "unsafe_no_drop_flag",
-    "packed", "simd", "repr", "deriving", "unsafe_destructor",
+    "packed", "simd", "repr", "deriving", "unsafe_destructor", "must_use",
 
     //mod-level
     "path", "link_name", "link_args", "nolink", "macro_escape", "no_implicit_prelude",
@@ -891,6 +904,82 @@ fn check_path_statement(cx: &Context, s: &ast::Stmt) {
     }
 }
 
+// Warns about statements which throw away a value whose type, or the
+// function returning it, is marked `#[must_use]`.
+fn check_unused_must_use(cx: &Context, s: &ast::Stmt) {
+    let expr = match s.node {
+        ast::StmtSemi(expr, _) => expr,
+        _ => return
+    };
+    let t = ty::expr_ty(cx.tcx, expr);
+    match ty::get(t).sty {
+        ty::ty_enum(did, _) | ty::ty_struct(did, _)
+                if ty::has_attr(cx.tcx, did, "must_use") => {
+            let name = ast_map::path_elt_to_str(*ty::item_path(cx.tcx, did).last(),
+                                                token::get_ident_interner());
+            cx.span_lint(unused_must_use, s.span,
+                         format!("unused `{}` that must be used", name));
+            return;
+        }
+        _ => {}
+    }
+    match expr.node {
+        ast::ExprCall(callee, _, _) => {
+            match cx.tcx.def_map.find(&callee.id) {
+                Some(&ast::DefFn(did, _)) if ty::has_attr(cx.tcx, did, "must_use") => {
+                    cx.span_lint(unused_must_use, s.span,
+                                 format!("unused return value of `{}` that must be used",
+                                         ty::item_path_str(cx.tcx, did)));
+                }
+                Some(&ast::DefStaticMethod(did, _, _)) if method_has_must_use(cx, did) => {
+                    cx.span_lint(unused_must_use, s.span,
+                                 format!("unused return value of `{}` that must be used",
+                                         ty::item_path_str(cx.tcx, did)));
+                }
+                _ => {}
+            }
+        }
+        ast::ExprMethodCall(*) => {
+            let did = match cx.method_map.find(&expr.id) {
+                Some(entry) => match entry.origin {
+                    typeck::method_static(did) => did,
+                    typeck::method_param(ref p) => {
+                        ty::trait_method(cx.tcx, p.trait_id, p.method_num).def_id
+                    }
+                    typeck::method_object(ref o) => {
+                        ty::trait_method(cx.tcx, o.trait_id, o.method_num).def_id
+                    }
+                },
+                None => return
+            };
+            if method_has_must_use(cx, did) {
+                cx.span_lint(unused_must_use, s.span,
+                             format!("unused return value of `{}` that must be used",
+                                     ty::item_path_str(cx.tcx, did)));
+            }
+        }
+        _ => {}
+    }
+}
+
+// Whether the method `did` is marked `#[must_use]`. Local methods are not
+// items, so `ty::has_attr` can't be used for them.
+fn method_has_must_use(cx: &Context, did: ast::DefId) -> bool {
+    if !ast_util::is_local(did) {
+        return ty::has_attr(cx.tcx, did, "must_use");
+    }
+    match cx.tcx.items.find(&did.node) {
+        Some(&ast_map::node_method(m, _, _)) => attr::contains_name(m.attrs, "must_use"),
+        Some(&ast_map::node_trait_method(m, _, _)) => {
+            match *m {
+                ast::required(ref m) => attr::contains_name(m.attrs, "must_use"),
+                ast::provided(m) => attr::contains_name(m.attrs, "must_use"),
+            }
+        }
+        _ => false
+    }
+}
+
 fn check_item_non_camel_case_types(cx: &Context, it: &ast::item) {
     fn is_camel_case(cx: ty::ctxt, ident: ast::Ident) -> bool {
         let ident = cx.sess.str_of(ident);
@@ -965,6 +1054,10 @@ fn check_pat_non_uppercase_statics(cx: &Context, p: &ast::Pat) {
 fn check_unused_unsafe(cx: &Context, e: &ast::Expr) {
     match e.node {
         // Don't warn about generated blocks, that'll just pollute the output.
+        //
+        // Code removed by `#[cfg]` needs no special treatment: only items
+        // and view items can be configured out of a block, and the unsafe
+        // operations inside a nested item never count towards the block.
         ast::ExprBlock(ref blk) => {
             if blk.rules == ast::UnsafeBlock(ast::UserProvided) &&
                 !cx.tcx.used_unsafe.contains(&blk.id) {
@@ -1253,6 +1346,7 @@ impl<'self> Visitor<()> for Context<'self> {
 
     fn visit_stmt(&mut self, s: @ast::Stmt, _: ()) {
         check_path_statement(self, s);
+        check_unused_must_use(self, s);
 
         visit::walk_stmt(self, s, ());
     }
@@ -1334,12 +1428,14 @@ impl<'self> ast_util::IdVisitingOperation for Context<'self> {
 }
 
 pub fn check_crate(tcx: ty::ctxt,
+                   method_map: typeck::method_map,
                    exported_items: &privacy::ExportedItems,
                    crate: &ast::Crate) {
     let mut cx = Context {
         dict: @get_lint_dict(),
         cur: SmallIntMap::new(),
         tcx: tcx,
+        method_map: method_map,
         exported_items: exported_items,
         cur_struct_def_id: -1,
         is_doc_hidden: false,
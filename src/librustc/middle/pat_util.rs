@@ -10,11 +10,12 @@
 
 
 use middle::resolve;
+use middle::ty;
 
 use std::hashmap::HashMap;
 use syntax::ast::*;
 use syntax::ast_util::{path_to_ident, walk_pat};
-use syntax::codemap::Span;
+use syntax::codemap::{Span, Spanned};
 
 pub type PatIdMap = HashMap<Ident, NodeId>;
 
@@ -106,3 +107,41 @@ pub fn pat_contains_bindings(dm: resolve::DefMap, pat: &Pat) -> bool {
     });
     contains_bindings
 }
+
+/// Checks whether a pattern matches every value of its type, as the
+/// patterns of `let` statements and function arguments must.
+pub fn pat_is_irrefutable(tcx: ty::ctxt, pat: &Pat) -> bool {
+    match tcx.def_map.find(&pat.id) {
+      Some(&DefVariant(enum_id, _, _)) => {
+        if ty::enum_variants(tcx, enum_id).len() != 1u {
+            return false;
+        }
+      }
+      Some(&DefStatic(*)) => return false,
+      _ => ()
+    }
+
+    match pat.node {
+      PatBox(sub) | PatUniq(sub) | PatRegion(sub) |
+      PatIdent(_, _, Some(sub)) => {
+        pat_is_irrefutable(tcx, sub)
+      }
+      PatWild | PatWildMulti | PatIdent(_, _, None) => { true }
+      PatLit(@Expr {node: ExprLit(@Spanned { node: lit_nil, _}), _}) => {
+        // "()"
+        true
+      }
+      PatLit(_) | PatRange(_, _) => { false }
+      PatStruct(_, ref fields, _) => {
+        fields.iter().all(|f| pat_is_irrefutable(tcx, f.pat))
+      }
+      PatTup(ref elts) => {
+        elts.iter().all(|elt| pat_is_irrefutable(tcx, *elt))
+      }
+      PatEnum(_, Some(ref args)) => {
+        args.iter().all(|a| pat_is_irrefutable(tcx, *a))
+      }
+      PatEnum(_,_) => { true }
+      PatVec(*) => { false }
+    }
+}
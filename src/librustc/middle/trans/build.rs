@@ -347,6 +347,16 @@ pub fn Load(cx: &Block, PointerVal: ValueRef) -> ValueRef {
     }
 }
 
+pub fn VolatileLoad(cx: &Block, PointerVal: ValueRef) -> ValueRef {
+    unsafe {
+        if cx.unreachable {
+            let eltty = val_ty(PointerVal).element_type();
+            return llvm::LLVMGetUndef(eltty.to_ref());
+        }
+        B(cx).volatile_load(PointerVal)
+    }
+}
+
 pub fn AtomicLoad(cx: &Block, PointerVal: ValueRef, order: AtomicOrdering) -> ValueRef {
     unsafe {
         let ccx = cx.fcx.ccx;
@@ -381,6 +391,11 @@ pub fn Store(cx: &Block, Val: ValueRef, Ptr: ValueRef) {
     B(cx).store(Val, Ptr)
 }
 
+pub fn VolatileStore(cx: &Block, Val: ValueRef, Ptr: ValueRef) {
+    if cx.unreachable { return; }
+    B(cx).volatile_store(Val, Ptr)
+}
+
 pub fn AtomicStore(cx: &Block, Val: ValueRef, Ptr: ValueRef, order: AtomicOrdering) {
     if cx.unreachable { return; }
     B(cx).atomic_store(Val, Ptr, order)
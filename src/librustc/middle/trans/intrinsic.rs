@@ -312,6 +312,25 @@ pub fn trans_intrinsic(ccx: @mut CrateContext,
         "forget" => {
             RetVoid(bcx);
         }
+        "volatile_load" => {
+            let tp_ty = substs.tys[0];
+            let val = VolatileLoad(bcx, get_param(decl, first_real_arg));
+            match bcx.fcx.llretptr {
+                Some(ptr) => { Store(bcx, val, ptr); RetVoid(bcx); }
+                None if ty::type_is_nil(tp_ty) => RetVoid(bcx),
+                None => Ret(bcx, val),
+            }
+        }
+        "volatile_store" => {
+            // Non-immediate values are passed by reference.
+            let tp_ty = substs.tys[0];
+            let mut val = get_param(decl, first_real_arg + 1u);
+            if !type_is_immediate(ccx, tp_ty) {
+                val = Load(bcx, val);
+            }
+            VolatileStore(bcx, val, get_param(decl, first_real_arg));
+            RetVoid(bcx);
+        }
         "transmute" => {
             let (in_type, out_type) = (substs.tys[0], substs.tys[1]);
             let llintype = type_of::type_of(ccx, in_type);
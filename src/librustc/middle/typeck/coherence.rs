@@ -420,35 +420,40 @@ impl CoherenceChecker {
     }
 
     pub fn check_implementation_coherence_of(&self, trait_def_id: DefId) {
-        // Unify pairs of polytypes.
-        self.iter_impls_of_trait(trait_def_id, |a| {
-            let implementation_a = a;
+        let impls = match self.crate_context.tcx.trait_impls.find(&trait_def_id) {
+            Some(&impls) => impls,
+            None => return
+        };
+
+        // Unify each pair of polytypes once, reporting the later impl of
+        // the pair as the conflicting one.
+        for (i, &implementation_a) in impls.iter().enumerate() {
             let polytype_a =
                 self.get_self_type_for_implementation(implementation_a);
 
             // "We have an impl of trait <trait_def_id> for type <polytype_a>,
             // and that impl is <implementation_a>"
-            self.iter_impls_of_trait(trait_def_id, |b| {
-                let implementation_b = b;
-
+            for &implementation_b in impls.slice_from(i + 1).iter() {
                 // An impl is coherent with itself
-                if a.did != b.did {
-                    let polytype_b = self.get_self_type_for_implementation(
-                            implementation_b);
-
-                    if self.polytypes_unify(polytype_a, polytype_b) {
-                        let session = self.crate_context.tcx.sess;
-                        session.span_err(
-                            self.span_of_impl(implementation_b),
-                            format!("conflicting implementations for trait `{}`",
-                                 ty::item_path_str(self.crate_context.tcx,
-                                                   trait_def_id)));
-                        session.span_note(self.span_of_impl(implementation_a),
-                                          "note conflicting implementation here");
-                    }
+                if implementation_a.did == implementation_b.did {
+                    continue;
                 }
-            })
-        })
+
+                let polytype_b = self.get_self_type_for_implementation(
+                        implementation_b);
+
+                if self.polytypes_unify(polytype_a, polytype_b) {
+                    let session = self.crate_context.tcx.sess;
+                    session.span_err(
+                        self.span_of_impl(implementation_b),
+                        format!("conflicting implementations for trait `{}`",
+                             ty::item_path_str(self.crate_context.tcx,
+                                               trait_def_id)));
+                    session.span_note(self.span_of_impl(implementation_a),
+                                      "note conflicting implementation here");
+                }
+            }
+        }
     }
 
     pub fn iter_impls_of_trait(&self, trait_def_id: DefId, f: |@Impl|) {
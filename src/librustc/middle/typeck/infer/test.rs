@@ -59,7 +59,7 @@ fn setup_env(test_name: &str, source_string: &str) -> Env {
     let freevars = HashMap();
     let region_paramd_items = HashMap();
     let region_map = HashMap();
-    let lang_items = LanguageItems::new();
+    let lang_items = @LanguageItems::new();
 
     let parse_sess = parse::new_parse_sess(None);
     let crate = parse_crate_from_source_str(
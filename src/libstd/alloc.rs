@@ -0,0 +1,149 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/*!
+ * An interface for memory allocators.
+ *
+ * A `Layout` describes the size and alignment of a block of memory, and a
+ * `GlobalAlloc` hands out and takes back such blocks. `System` is the
+ * allocator backed by the C library's `malloc` and `free`.
+ *
+ * A crate selects its allocator by marking a static `#[global_allocator]`;
+ * the compiler records the choice, and rejects a second one. The exchange
+ * heap does not allocate through it yet: `exchange_free` is not given the
+ * size of the block it frees, which `GlobalAlloc::dealloc` needs.
+ *
+ * ~~~ {.rust}
+ * use std::alloc::System;
+ *
+ * #[global_allocator]
+ * static ALLOC: System = System;
+ * ~~~
+ */
+
+use libc::{c_void, size_t, malloc, free};
+use mem;
+use option::{Option, Some, None};
+use ptr::RawPtr;
+
+/// The size and alignment of a block of memory.
+#[deriving(Clone, Eq)]
+pub struct Layout {
+    priv size: uint,
+    priv align: uint,
+}
+
+impl Layout {
+    /// The layout of a value of type `T`.
+    #[inline]
+    pub fn new<T>() -> Layout {
+        Layout { size: mem::size_of::<T>(), align: mem::min_align_of::<T>() }
+    }
+
+    /// A layout of `size` bytes aligned to `align`, or `None` if `align` is
+    /// not a power of two.
+    #[inline]
+    pub fn from_size_align(size: uint, align: uint) -> Option<Layout> {
+        if align == 0 || align & (align - 1) != 0 {
+            return None;
+        }
+        Some(Layout { size: size, align: align })
+    }
+
+    /// The size of the block, in bytes.
+    #[inline]
+    pub fn size(&self) -> uint { self.size }
+
+    /// The alignment of the block, in bytes.
+    #[inline]
+    pub fn align(&self) -> uint { self.align }
+}
+
+/// An allocator of memory blocks described by `Layout`s.
+pub trait GlobalAlloc {
+    /// Allocate a block of memory matching `layout`, returning null if the
+    /// request cannot be satisfied. The contents of the block are undefined.
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8;
+
+    /// Free a block returned by `alloc` with the same `layout`.
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout);
+}
+
+/// The allocator of the C library.
+pub struct System;
+
+// The alignment `malloc` guarantees on the supported platforms.
+#[cfg(target_word_size = "32")]
+static MALLOC_ALIGN: uint = 8;
+#[cfg(target_word_size = "64")]
+static MALLOC_ALIGN: uint = 16;
+
+impl GlobalAlloc for System {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if layout.align <= MALLOC_ALIGN {
+            return malloc(layout.size as size_t) as *mut u8;
+        }
+
+        // Over-allocate, and keep the pointer returned by `malloc` just
+        // before the aligned block so that `dealloc` can find it.
+        let header = mem::size_of::<*c_void>();
+        let raw = malloc((layout.size + layout.align + header) as size_t);
+        if raw.is_null() {
+            return raw as *mut u8;
+        }
+        let start = raw as uint + header;
+        let aligned = (start + layout.align - 1) & !(layout.align - 1);
+        *((aligned - header) as *mut *c_void) = raw;
+        aligned as *mut u8
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if layout.align <= MALLOC_ALIGN {
+            free(ptr as *c_void);
+        } else {
+            let header = mem::size_of::<*c_void>();
+            free(*((ptr as uint - header) as *mut *c_void));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prelude::*;
+    use super::*;
+    use mem;
+
+    #[test]
+    fn test_layout() {
+        let l = Layout::new::<u64>();
+        assert_eq!(l.size(), 8);
+        assert_eq!(l.align(), mem::min_align_of::<u64>());
+
+        assert!(Layout::from_size_align(16, 8).is_some());
+        assert!(Layout::from_size_align(16, 0).is_none());
+        assert!(Layout::from_size_align(16, 12).is_none());
+    }
+
+    #[test]
+    fn test_system_alloc() {
+        for &align in [1u, 8, 64, 4096].iter() {
+            let layout = Layout::from_size_align(100, align).unwrap();
+            unsafe {
+                let p = System.alloc(layout);
+                assert!(!p.is_null());
+                assert_eq!(p as uint % align, 0);
+                *p = 7;
+                *p.offset(99) = 9;
+                assert_eq!(*p + *p.offset(99), 16);
+                System.dealloc(p, layout);
+            }
+        }
+    }
+}
@@ -11,12 +11,14 @@
 //! Utilities for manipulating the char type
 
 use cast::transmute;
+use container::Container;
 use option::{None, Option, Some};
 use iter::{Iterator, range_step};
 use str::StrSlice;
-use unicode::{derived_property, general_category, decompose};
+use unicode::{derived_property, general_category, decompose, conversions};
 use to_str::ToStr;
 use str;
+use vec::ImmutableVector;
 
 #[cfg(test)] use str::OwnedStr;
 
@@ -144,6 +146,136 @@ pub fn is_digit(c: char) -> bool {
         || general_category::No(c)
 }
 
+/// Returns an iterator over the characters `c` maps to when uppercased,
+/// e.g. `'ß'` gives `'S'` twice. Language and context sensitive mappings
+/// are not applied.
+#[inline]
+pub fn to_uppercase(c: char) -> ToUppercase {
+    ToUppercase { iter: CaseMappingIter::new(c, conversions::to_upper(c)) }
+}
+
+/// Returns an iterator over the characters `c` maps to when lowercased.
+/// Language and context sensitive mappings are not applied.
+#[inline]
+pub fn to_lowercase(c: char) -> ToLowercase {
+    ToLowercase { iter: CaseMappingIter::new(c, conversions::to_lower(c)) }
+}
+
+/// An iterator over the uppercase mapping of a character, one to three
+/// characters long. See `to_uppercase`.
+pub struct ToUppercase {
+    priv iter: CaseMappingIter,
+}
+
+/// An iterator over the lowercase mapping of a character, one or two
+/// characters long. See `to_lowercase`.
+pub struct ToLowercase {
+    priv iter: CaseMappingIter,
+}
+
+// Either the character itself, or the characters from the conversion table.
+struct CaseMappingIter {
+    single: Option<char>,
+    mapped: &'static [char],
+}
+
+impl CaseMappingIter {
+    fn new(c: char, mapped: Option<&'static [char]>) -> CaseMappingIter {
+        match mapped {
+            Some(chars) => CaseMappingIter { single: None, mapped: chars },
+            None => CaseMappingIter { single: Some(c), mapped: &[] },
+        }
+    }
+
+    fn next(&mut self) -> Option<char> {
+        match self.single.take() {
+            Some(c) => Some(c),
+            None if self.mapped.is_empty() => None,
+            None => {
+                let c = self.mapped[0];
+                self.mapped = self.mapped.slice_from(1);
+                Some(c)
+            }
+        }
+    }
+
+    fn len(&self) -> uint {
+        self.mapped.len() + if self.single.is_some() { 1 } else { 0 }
+    }
+}
+
+impl Iterator<char> for ToUppercase {
+    #[inline]
+    fn next(&mut self) -> Option<char> { self.iter.next() }
+
+    #[inline]
+    fn size_hint(&self) -> (uint, Option<uint>) {
+        let n = self.iter.len();
+        (n, Some(n))
+    }
+}
+
+impl Iterator<char> for ToLowercase {
+    #[inline]
+    fn next(&mut self) -> Option<char> { self.iter.next() }
+
+    #[inline]
+    fn size_hint(&self) -> (uint, Option<uint>) {
+        let n = self.iter.len();
+        (n, Some(n))
+    }
+}
+
+/// Indicates whether a character is an ASCII letter, `A-Z` or `a-z`
+#[inline]
+pub fn is_ascii_alphabetic(c: char) -> bool {
+    is_ascii_uppercase(c) || is_ascii_lowercase(c)
+}
+
+/// Indicates whether a character is an ASCII uppercase letter, `A-Z`
+#[inline]
+pub fn is_ascii_uppercase(c: char) -> bool { 'A' <= c && c <= 'Z' }
+
+/// Indicates whether a character is an ASCII lowercase letter, `a-z`
+#[inline]
+pub fn is_ascii_lowercase(c: char) -> bool { 'a' <= c && c <= 'z' }
+
+/// Indicates whether a character is an ASCII decimal digit, `0-9`
+#[inline]
+pub fn is_ascii_digit(c: char) -> bool { '0' <= c && c <= '9' }
+
+/// Indicates whether a character is an ASCII letter or decimal digit
+#[inline]
+pub fn is_ascii_alphanumeric(c: char) -> bool {
+    is_ascii_alphabetic(c) || is_ascii_digit(c)
+}
+
+///
+/// Indicates whether a character is ASCII whitespace: space, tab, line
+/// feed, form feed or carriage return. Unlike `is_whitespace`, the
+/// vertical tab is not included.
+///
+#[inline]
+pub fn is_ascii_whitespace(c: char) -> bool {
+    match c {
+        ' ' | '\t' | '\n' | '\x0c' | '\r' => true,
+        _ => false
+    }
+}
+
+/// Indicates whether a character is ASCII punctuation, i.e. a printable
+/// ASCII character that is neither a letter, a digit nor a space
+#[inline]
+pub fn is_ascii_punctuation(c: char) -> bool {
+    ('!' <= c && c <= '/') || (':' <= c && c <= '@') ||
+        ('[' <= c && c <= '`') || ('{' <= c && c <= '~')
+}
+
+/// Indicates whether a character is an ASCII control character, `\x00`
+/// to `\x1f` or `\x7f`
+#[inline]
+pub fn is_ascii_control(c: char) -> bool { c < ' ' || c == '\x7f' }
+
 ///
 /// Checks if a character parses as a numeric digit in the given radix.
 /// Compared to `is_digit()`, this function only recognizes the
@@ -365,6 +497,16 @@ pub trait Char {
     fn is_control(&self) -> bool;
     fn is_digit(&self) -> bool;
     fn is_digit_radix(&self, radix: uint) -> bool;
+    fn to_uppercase(&self) -> ToUppercase;
+    fn to_lowercase(&self) -> ToLowercase;
+    fn is_ascii_alphabetic(&self) -> bool;
+    fn is_ascii_uppercase(&self) -> bool;
+    fn is_ascii_lowercase(&self) -> bool;
+    fn is_ascii_digit(&self) -> bool;
+    fn is_ascii_alphanumeric(&self) -> bool;
+    fn is_ascii_whitespace(&self) -> bool;
+    fn is_ascii_punctuation(&self) -> bool;
+    fn is_ascii_control(&self) -> bool;
     fn to_digit(&self, radix: uint) -> Option<uint>;
     fn from_digit(num: uint, radix: uint) -> Option<char>;
     fn escape_unicode(&self, f: |char|);
@@ -399,6 +541,26 @@ impl Char for char {
 
     fn is_digit_radix(&self, radix: uint) -> bool { is_digit_radix(*self, radix) }
 
+    fn to_uppercase(&self) -> ToUppercase { to_uppercase(*self) }
+
+    fn to_lowercase(&self) -> ToLowercase { to_lowercase(*self) }
+
+    fn is_ascii_alphabetic(&self) -> bool { is_ascii_alphabetic(*self) }
+
+    fn is_ascii_uppercase(&self) -> bool { is_ascii_uppercase(*self) }
+
+    fn is_ascii_lowercase(&self) -> bool { is_ascii_lowercase(*self) }
+
+    fn is_ascii_digit(&self) -> bool { is_ascii_digit(*self) }
+
+    fn is_ascii_alphanumeric(&self) -> bool { is_ascii_alphanumeric(*self) }
+
+    fn is_ascii_whitespace(&self) -> bool { is_ascii_whitespace(*self) }
+
+    fn is_ascii_punctuation(&self) -> bool { is_ascii_punctuation(*self) }
+
+    fn is_ascii_control(&self) -> bool { is_ascii_control(*self) }
+
     fn to_digit(&self, radix: uint) -> Option<uint> { to_digit(*self, radix) }
 
     fn from_digit(num: uint, radix: uint) -> Option<char> { from_digit(num, radix) }
@@ -570,3 +732,46 @@ fn test_to_str() {
     let s = 't'.to_str();
     assert_eq!(s, ~"t");
 }
+
+#[test]
+fn test_is_ascii_classes() {
+    assert!('a'.is_ascii_alphabetic() && 'Z'.is_ascii_alphabetic());
+    assert!(!'1'.is_ascii_alphabetic() && !'\u00e9'.is_ascii_alphabetic());
+    assert!('Q'.is_ascii_uppercase() && !'q'.is_ascii_uppercase());
+    assert!('q'.is_ascii_lowercase() && !'Q'.is_ascii_lowercase());
+    assert!('0'.is_ascii_digit() && '9'.is_ascii_digit());
+    assert!(!'a'.is_ascii_digit() && !'\u0663'.is_ascii_digit());
+    assert!('x'.is_ascii_alphanumeric() && '7'.is_ascii_alphanumeric());
+    assert!(!'_'.is_ascii_alphanumeric());
+    assert!(' '.is_ascii_whitespace() && '\t'.is_ascii_whitespace());
+    assert!(!'\x0b'.is_ascii_whitespace() && !'\u00a0'.is_ascii_whitespace());
+    for c in "!/:@[`{~".chars() {
+        assert!(c.is_ascii_punctuation());
+    }
+    assert!(!' '.is_ascii_punctuation() && !'a'.is_ascii_punctuation());
+    assert!('\x00'.is_ascii_control() && '\x1f'.is_ascii_control());
+    assert!('\x7f'.is_ascii_control() && !' '.is_ascii_control());
+    assert!(!'\x80'.is_ascii_control());
+}
+
+#[test]
+fn test_to_uppercase() {
+    fn upper(c: char) -> ~str { c.to_uppercase().collect() }
+    assert_eq!(upper('a'), ~"A");
+    assert_eq!(upper('A'), ~"A");
+    assert_eq!(upper('1'), ~"1");
+    assert_eq!(upper('\u00e9'), ~"\u00c9");
+    assert_eq!(upper('\u00df'), ~"SS");
+    assert_eq!(upper('\u0390'), ~"\u0399\u0308\u0301");
+    assert_eq!('\u00df'.to_uppercase().size_hint(), (2, Some(2)));
+}
+
+#[test]
+fn test_to_lowercase() {
+    fn lower(c: char) -> ~str { c.to_lowercase().collect() }
+    assert_eq!(lower('A'), ~"a");
+    assert_eq!(lower('a'), ~"a");
+    assert_eq!(lower('\u03a3'), ~"\u03c3");
+    assert_eq!(lower('\u0130'), ~"i\u0307");
+    assert_eq!('x'.to_lowercase().size_hint(), (1, Some(1)));
+}
@@ -0,0 +1,254 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/*!
+ * Traits for conversions between types.
+ *
+ * Implement `From` (or `TryFrom` for conversions that can fail); the
+ * matching `Into` and `TryInto` implementations are then provided for the
+ * source type automatically.
+ *
+ * Coherence only distinguishes implementations by their `Self` type, so a
+ * type can currently implement `From` for a single source type. For the
+ * same reason there is no identity `From<T> for T`, which would rule out
+ * every other implementation.
+ *
+ * ~~~ {.rust}
+ * use std::convert::{From, Into};
+ *
+ * let a: ~str = From::from("abc");
+ * let b: ~[int] = (&[1, 2, 3]).into();
+ * ~~~
+ */
+
+use clone::Clone;
+use result::Result;
+use str::StrSlice;
+use vec::CopyableVector;
+
+/// A conversion from `T` to `Self` that cannot fail.
+pub trait From<T> {
+    /// Convert `t` into a value of this type.
+    fn from(t: T) -> Self;
+}
+
+/// A conversion from `Self` to `T` that cannot fail. Implement `From`
+/// instead of this trait.
+pub trait Into<T> {
+    /// Convert this value into a `T`.
+    fn into(self) -> T;
+}
+
+/// A conversion from `T` to `Self` that may fail with an `E`.
+pub trait TryFrom<T, E> {
+    /// Convert `t` into a value of this type, or fail with an error.
+    fn try_from(t: T) -> Result<Self, E>;
+}
+
+/// A conversion from `Self` to `T` that may fail with an `E`. Implement
+/// `TryFrom` instead of this trait.
+pub trait TryInto<T, E> {
+    /// Convert this value into a `T`, or fail with an error.
+    fn try_into(self) -> Result<T, E>;
+}
+
+/// A cheap conversion from a reference to `Self` to a reference to `T`.
+///
+/// Strings and vectors are borrowed through `Str` and `Vector`, since `T`
+/// has to be a sized type.
+pub trait AsRef<T> {
+    /// Borrow this value as a `&T`.
+    fn as_ref<'a>(&'a self) -> &'a T;
+}
+
+/// A cheap conversion from a mutable reference to `Self` to a mutable
+/// reference to `T`.
+pub trait AsMut<T> {
+    /// Borrow this value as a `&mut T`.
+    fn as_mut<'a>(&'a mut self) -> &'a mut T;
+}
+
+/// Borrowing `Self` as a `&T` that behaves like the whole value.
+///
+/// Unlike `AsRef`, implementations promise that `Eq`, `Ord` and `Hash` give
+/// the same answers for the borrowed `T` as for `Self`. Generic code can
+/// therefore compare or hash through `borrow`, e.g. to look up keys.
+pub trait Borrow<T> {
+    /// Immutably borrow this value as a `&T`.
+    fn borrow<'a>(&'a self) -> &'a T;
+}
+
+/// The mutable counterpart of `Borrow`.
+pub trait BorrowMut<T> : Borrow<T> {
+    /// Mutably borrow this value as a `&mut T`.
+    fn borrow_mut<'a>(&'a mut self) -> &'a mut T;
+}
+
+impl<T, U: From<T>> Into<U> for T {
+    #[inline]
+    fn into(self) -> U { From::from(self) }
+}
+
+impl<T, U: TryFrom<T, E>, E> TryInto<U, E> for T {
+    #[inline]
+    fn try_into(self) -> Result<U, E> { TryFrom::try_from(self) }
+}
+
+impl<'self> From<&'self str> for ~str {
+    #[inline]
+    fn from(s: &'self str) -> ~str { s.to_owned() }
+}
+
+impl<'self, T: Clone> From<&'self [T]> for ~[T] {
+    #[inline]
+    fn from(v: &'self [T]) -> ~[T] { v.to_owned() }
+}
+
+impl<'self, T> AsRef<T> for &'self T {
+    #[inline]
+    fn as_ref<'a>(&'a self) -> &'a T { &**self }
+}
+
+impl<'self, T> AsRef<T> for &'self mut T {
+    #[inline]
+    fn as_ref<'a>(&'a self) -> &'a T { &**self }
+}
+
+impl<T> AsRef<T> for ~T {
+    #[inline]
+    fn as_ref<'a>(&'a self) -> &'a T { &**self }
+}
+
+impl<T> AsRef<T> for @T {
+    #[inline]
+    fn as_ref<'a>(&'a self) -> &'a T { &**self }
+}
+
+impl<'self, T> AsMut<T> for &'self mut T {
+    #[inline]
+    fn as_mut<'a>(&'a mut self) -> &'a mut T { &mut **self }
+}
+
+impl<T> AsMut<T> for ~T {
+    #[inline]
+    fn as_mut<'a>(&'a mut self) -> &'a mut T { &mut **self }
+}
+
+impl<'self, T> Borrow<T> for &'self T {
+    #[inline]
+    fn borrow<'a>(&'a self) -> &'a T { &**self }
+}
+
+impl<'self, T> Borrow<T> for &'self mut T {
+    #[inline]
+    fn borrow<'a>(&'a self) -> &'a T { &**self }
+}
+
+impl<T> Borrow<T> for ~T {
+    #[inline]
+    fn borrow<'a>(&'a self) -> &'a T { &**self }
+}
+
+impl<T> Borrow<T> for @T {
+    #[inline]
+    fn borrow<'a>(&'a self) -> &'a T { &**self }
+}
+
+impl<'self, T> BorrowMut<T> for &'self mut T {
+    #[inline]
+    fn borrow_mut<'a>(&'a mut self) -> &'a mut T { &mut **self }
+}
+
+impl<T> BorrowMut<T> for ~T {
+    #[inline]
+    fn borrow_mut<'a>(&'a mut self) -> &'a mut T { &mut **self }
+}
+
+#[cfg(test)]
+mod tests {
+    use prelude::*;
+    use super::*;
+
+    #[test]
+    fn test_from() {
+        let s: ~str = From::from("abc");
+        assert_eq!(s, ~"abc");
+        let v: ~[int] = From::from(&[1, 2, 3]);
+        assert_eq!(v, ~[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_into() {
+        let s: ~str = "hi".into();
+        assert_eq!(s, ~"hi");
+    }
+
+    #[test]
+    fn test_as_ref() {
+        fn get<R: AsRef<int>>(r: R) -> int { *r.as_ref() }
+
+        let x = 5;
+        assert_eq!(get(&x), 5);
+        assert_eq!(get(~6), 6);
+        assert_eq!(get(@7), 7);
+    }
+
+    #[test]
+    fn test_as_mut() {
+        fn bump<R: AsMut<int>>(r: &mut R) { *r.as_mut() += 1; }
+
+        let mut b = ~1;
+        bump(&mut b);
+        assert_eq!(*b, 2);
+
+        let mut x = 1;
+        {
+            let mut r = &mut x;
+            bump(&mut r);
+        }
+        assert_eq!(x, 2);
+    }
+
+    #[test]
+    fn test_borrow() {
+        fn position<K: Borrow<int>>(keys: &[K], key: &int) -> Option<uint> {
+            keys.iter().position(|k| k.borrow() == key)
+        }
+
+        let keys = ~[~1, ~2, ~3];
+        assert_eq!(position(keys, &2), Some(1));
+        assert_eq!(position(keys, &4), None);
+        assert_eq!(position([@5], &5), Some(0));
+    }
+
+    #[test]
+    fn test_borrow_mut() {
+        let mut b = ~5;
+        *b.borrow_mut() += 1;
+        assert_eq!(*b.borrow(), 6);
+    }
+
+    struct Even(uint);
+
+    impl TryFrom<uint, ~str> for Even {
+        fn try_from(n: uint) -> Result<Even, ~str> {
+            if n % 2 == 0 { Ok(Even(n)) } else { Err(format!("{} is odd", n)) }
+        }
+    }
+
+    #[test]
+    fn test_try_from() {
+        let even: Result<Even, ~str> = TryFrom::try_from(4u);
+        assert_eq!(*even.unwrap(), 4);
+
+        let odd: Result<Even, ~str> = 3u.try_into();
+        assert_eq!(odd.unwrap_err(), ~"3 is odd");
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/*!
+ * The `Error` trait, a common interface for error values.
+ *
+ * Errors describe themselves briefly through `description` and in full,
+ * with any details, through `to_str`. An error caused by another error can
+ * expose it through `cause`, so that callers can walk the whole chain:
+ *
+ * ~~~ {.rust}
+ * use std::error::Error;
+ *
+ * fn report(err: &Error) {
+ *     println(err.description());
+ *     let mut cur = err.cause();
+ *     loop {
+ *         match cur {
+ *             Some(e) => { println!("caused by: {}", e.description()); cur = e.cause(); }
+ *             None => break
+ *         }
+ *     }
+ * }
+ * ~~~
+ */
+
+use option::{Option, None};
+use to_str::ToStr;
+
+/// Base functionality for all error types.
+pub trait Error: ToStr {
+    /// A short description of the error, without any details.
+    fn description<'a>(&'a self) -> &'a str;
+
+    /// The lower-level error that caused this one, if any.
+    fn cause<'a>(&'a self) -> Option<&'a Error> { None }
+}
+
+#[cfg(test)]
+mod tests {
+    use prelude::*;
+    use super::*;
+
+    struct Low;
+
+    impl ToStr for Low {
+        fn to_str(&self) -> ~str { ~"low-level failure" }
+    }
+
+    impl Error for Low {
+        fn description<'a>(&'a self) -> &'a str { "low" }
+    }
+
+    struct High {
+        low: Low,
+    }
+
+    impl ToStr for High {
+        fn to_str(&self) -> ~str { ~"high-level failure" }
+    }
+
+    impl Error for High {
+        fn description<'a>(&'a self) -> &'a str { "high" }
+        fn cause<'a>(&'a self) -> Option<&'a Error> { Some(&self.low as &Error) }
+    }
+
+    #[test]
+    fn test_cause_chain() {
+        let err = High { low: Low };
+        let err = &err as &Error;
+        assert_eq!(err.description(), "high");
+
+        let cause = err.cause().unwrap();
+        assert_eq!(cause.description(), "low");
+        assert!(cause.cause().is_none());
+    }
+}
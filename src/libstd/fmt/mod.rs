@@ -147,6 +147,8 @@ The current mapping of types to traits is:
 * `p` ⇒ `Pointer`
 * `t` ⇒ `Binary`
 * `f` ⇒ `Float`
+* `e` ⇒ `LowerExp`
+* `E` ⇒ `UpperExp`
 * *nothing* ⇒ `Default`
 
 What this means is that any type of argument which implements the
@@ -220,7 +222,8 @@ currently implemented are:
 
 ```rust
 format!      // described above
-write!       // first argument is a &mut io::Writer, the destination
+write!       // first argument is a &mut io::Writer, the destination (a
+             // fmt::Write buffer such as a ~str goes through a fmt::Adapter)
 writeln!     // same as write but appends a newline
 print!       // the format string is printed to the standard output
 println!     // same as print but appends a newline
@@ -242,6 +245,17 @@ let mut w = io::mem::MemWriter::new();
 write!(&mut w as &mut io::Writer, "Hello {}!", "world");
 ```
 
+In-memory buffers such as strings implement the `fmt::Write` trait instead,
+and are handed to `write!` through a `fmt::Adapter`:
+
+```rust
+use std::fmt;
+use std::io;
+
+let mut s = ~"";
+write!(&mut fmt::Adapter::new(&mut s) as &mut io::Writer, "Hello {}!", "world");
+```
+
 #### `print!`
 
 This and `println` emit their output to stdout. Similarly to the `write!` macro,
@@ -369,7 +383,7 @@ argument := integer | identifier
 
 format_spec := [[fill]align][sign]['#'][0][width]['.' precision][type]
 fill := character
-align := '<' | '>'
+align := '<' | '>' | '^'
 sign := '+' | '-'
 width := count
 precision := count | '*'
@@ -397,10 +411,12 @@ syntax draws heavily from Python's, so it may seem a bit familiar.
 The fill character is provided normally in conjunction with the `width`
 parameter. This indicates that if the value being formatted is smaller than
 `width` some extra characters will be printed around it. The extra characters
-are specified by `fill`, and the alignment can be one of two options:
+are specified by `fill`, and the alignment can be one of three options:
 
 * `<` - the argument is left-aligned in `width` columns
 * `>` - the argument is right-aligned in `width` columns
+* `^` - the argument is center-aligned in `width` columns, with any odd
+        column of padding placed on the right
 
 ### Sign/#/0
 
@@ -449,6 +465,11 @@ For integral types, this has no meaning currently.
 For floating-point types, this indicates how many digits after the decimal point
 should be printed.
 
+The `e` and `E` formats print floating-point numbers in scientific notation,
+as a mantissa in the range `[1, 10)` followed by a signed decimal exponent:
+`{:.2e}` formats `1234.5` as `1.23e+3`. The precision applies to the digits
+of the mantissa.
+
 ## Escaping
 
 The literal characters `{`, `}`, or `#` may be included in a string by
@@ -467,6 +488,7 @@ use io::mem::MemWriter;
 use io;
 use str;
 use repr;
+use task;
 use util;
 use vec;
 
@@ -481,7 +503,7 @@ pub struct Formatter<'self> {
     flags: uint,
     /// Character used as 'fill' whenever there is alignment
     fill: char,
-    /// Boolean indication of whether the output should be left-aligned
+    /// Where the output should be placed when it is padded out to `width`
     align: parse::Alignment,
     /// Optionally specified integer width that the output should be
     width: Option<uint>,
@@ -550,7 +572,7 @@ pub trait Unsigned { fn fmt(&Self, &mut Formatter); }
 /// Format trait for the `o` character
 #[allow(missing_doc)]
 pub trait Octal { fn fmt(&Self, &mut Formatter); }
-/// Format trait for the `b` character
+/// Format trait for the `t` character
 #[allow(missing_doc)]
 pub trait Binary { fn fmt(&Self, &mut Formatter); }
 /// Format trait for the `x` character
@@ -571,6 +593,80 @@ pub trait Pointer { fn fmt(&Self, &mut Formatter); }
 /// Format trait for the `f` character
 #[allow(missing_doc)]
 pub trait Float { fn fmt(&Self, &mut Formatter); }
+/// Format trait for the `e` character
+#[allow(missing_doc)]
+pub trait LowerExp { fn fmt(&Self, &mut Formatter); }
+/// Format trait for the `E` character
+#[allow(missing_doc)]
+pub trait UpperExp { fn fmt(&Self, &mut Formatter); }
+
+/// A buffer held in memory, such as a string, which formatted output can be
+/// appended to.
+pub trait Write {
+    /// Appends the string slice `s` to the end of this buffer.
+    fn write_str(&mut self, s: &str);
+}
+
+impl Write for ~str {
+    fn write_str(&mut self, s: &str) { self.push_str(s) }
+}
+
+/// Adapts a `Write` buffer to the byte-oriented `io::Writer` interface, so
+/// that it can be the destination of `write!` and `writeln!`.
+///
+/// Bytes are appended to the buffer once they form complete UTF-8 sequences.
+/// The start of a character which is split across two writes is held back
+/// until the rest of it arrives. Bytes which are not UTF-8 cause a failure,
+/// as does flushing or dropping the adapter in the middle of a character.
+pub struct Adapter<'a, W> {
+    priv dst: &'a mut W,
+    priv pending: ~[u8],
+}
+
+impl<'a, W: Write> Adapter<'a, W> {
+    /// Creates an adapter appending to `dst`.
+    pub fn new(dst: &'a mut W) -> Adapter<'a, W> {
+        Adapter { dst: dst, pending: ~[] }
+    }
+}
+
+impl<'a, W: Write> io::Writer for Adapter<'a, W> {
+    fn write(&mut self, buf: &[u8]) {
+        self.pending.push_all(buf);
+
+        let mut end = 0;
+        while end < self.pending.len() {
+            let width = str::utf8_char_width(self.pending[end]);
+            if width == 0 {
+                fail!("invalid UTF-8 byte {} written to a fmt::Adapter",
+                      self.pending[end]);
+            }
+            if end + width > self.pending.len() { break }
+            end += width;
+        }
+
+        if end > 0 {
+            self.dst.write_str(str::from_utf8_slice(self.pending.slice_to(end)));
+            self.pending = self.pending.slice_from(end).to_owned();
+        }
+    }
+
+    fn flush(&mut self) {
+        if !self.pending.is_empty() {
+            fail!("incomplete UTF-8 sequence written to a fmt::Adapter");
+        }
+    }
+}
+
+#[unsafe_destructor]
+impl<'a, W> Drop for Adapter<'a, W> {
+    fn drop(&mut self) {
+        // Failing again while already failing would abort the process.
+        if !self.pending.is_empty() && !task::failing() {
+            fail!("incomplete UTF-8 sequence written to a fmt::Adapter");
+        }
+    }
+}
 
 /// The `write` function takes an output stream, a precompiled format string,
 /// and a list of arguments. The arguments will be formatted according to the
@@ -937,18 +1033,22 @@ impl<'self> Formatter<'self> {
                     f: |&mut Formatter|) {
         let align = match self.align {
             parse::AlignUnknown => default,
-            parse::AlignLeft | parse::AlignRight => self.align
+            parse::AlignLeft | parse::AlignRight | parse::AlignCenter => self.align
+        };
+        // Centered output puts any odd column of padding on the right
+        let (pre_pad, post_pad) = match align {
+            parse::AlignLeft => (0, padding),
+            parse::AlignRight | parse::AlignUnknown => (padding, 0),
+            parse::AlignCenter => (padding / 2, (padding + 1) / 2),
         };
-        if align == parse::AlignLeft {
-            f(self);
-        }
         let mut fill = [0u8, ..4];
         let len = self.fill.encode_utf8(fill);
-        for _ in range(0, padding) {
+        for _ in range(0, pre_pad) {
             self.buf.write(fill.slice_to(len));
         }
-        if align == parse::AlignRight {
-            f(self);
+        f(self);
+        for _ in range(0, post_pad) {
+            self.buf.write(fill.slice_to(len));
         }
     }
 }
@@ -1084,6 +1184,61 @@ macro_rules! floating(($ty:ident) => {
 floating!(f32)
 floating!(f64)
 
+macro_rules! floating_exp(($ty:ident, $name:ident, $e:expr) => {
+    impl $name for $ty {
+        fn fmt(f: &$ty, fmt: &mut Formatter) {
+            let num = f.abs();
+            let precision = fmt.precision;
+            let mantissa = |m: $ty| -> ~str {
+                match precision {
+                    Some(i) => ::$ty::to_str_exact(m, i),
+                    None => ::$ty::to_str_digits(m, 6)
+                }
+            };
+
+            // XXX: this shouldn't perform an allocation
+            let s = if num != num || num == 1.0 / 0.0 {
+                // NaN and infinity have no exponent to speak of
+                mantissa(num)
+            } else if num == 0.0 {
+                let mut s = mantissa(num);
+                s.push_str($e);
+                s.push_str("+0");
+                s
+            } else {
+                // Scale by the power of ten in two steps so that neither
+                // factor overflows for the extreme exponents.
+                let scale = |exp: int| -> $ty {
+                    let half = exp / 2;
+                    num / (10.0 as $ty).pow(&(half as $ty))
+                        / (10.0 as $ty).pow(&((exp - half) as $ty))
+                };
+
+                // log10 may be off by one near powers of ten, and rounding
+                // the mantissa may carry it up to 10, so fix up both cases.
+                let mut exp = num.log10().floor() as int;
+                let m = scale(exp);
+                if m >= 10.0 { exp += 1 } else if m < 1.0 { exp -= 1 }
+                let mut digits = mantissa(scale(exp));
+                if digits.starts_with("10") {
+                    exp += 1;
+                    digits = mantissa(scale(exp));
+                }
+
+                digits.push_str($e);
+                digits.push_str(if exp < 0 { "-" } else { "+" });
+                digits.push_str((if exp < 0 { -exp } else { exp }).to_str());
+                digits
+            };
+            fmt.pad_integral(s.as_bytes(), "", *f >= 0.0);
+        }
+    }
+})
+floating_exp!(f32, LowerExp, "e")
+floating_exp!(f64, LowerExp, "e")
+floating_exp!(f32, UpperExp, "E")
+floating_exp!(f64, UpperExp, "E")
+
 impl<T> Poly for T {
     fn fmt(t: &T, f: &mut Formatter) {
         match (f.width, f.precision) {
@@ -1112,6 +1267,17 @@ impl<T> Pointer for *T {
 impl<T> Pointer for *mut T {
     fn fmt(t: &*mut T, f: &mut Formatter) { Pointer::fmt(&(*t as *T), f) }
 }
+impl<'self, T> Pointer for &'self T {
+    fn fmt(t: & &'self T, f: &mut Formatter) { Pointer::fmt(&(*t as *T), f) }
+}
+impl<'self, T> Pointer for &'self mut T {
+    fn fmt(t: & &'self mut T, f: &mut Formatter) {
+        Pointer::fmt(&(&**t as *T), f)
+    }
+}
+impl<T> Pointer for ~T {
+    fn fmt(t: &~T, f: &mut Formatter) { Pointer::fmt(&(&**t as *T), f) }
+}
 
 // Implementation of Default for various core types
 
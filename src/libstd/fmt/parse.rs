@@ -75,7 +75,7 @@ pub enum Position<'self> {
 /// Enum of alignments which are supported.
 #[deriving(Eq)]
 #[allow(missing_doc)]
-pub enum Alignment { AlignLeft, AlignRight, AlignUnknown }
+pub enum Alignment { AlignLeft, AlignRight, AlignCenter, AlignUnknown }
 
 /// Various flags which can be applied to format strings, the meaning of these
 /// flags is defined by the formatters themselves.
@@ -336,7 +336,7 @@ impl<'self> Parser<'self> {
         match self.cur.clone().next() {
             Some((_, c)) => {
                 match self.cur.clone().skip(1).next() {
-                    Some((_, '>')) | Some((_, '<')) => {
+                    Some((_, '>')) | Some((_, '<')) | Some((_, '^')) => {
                         spec.fill = Some(c);
                         self.cur.next();
                     }
@@ -350,6 +350,8 @@ impl<'self> Parser<'self> {
             spec.align = AlignLeft;
         } else if self.consume('>') {
             spec.align = AlignRight;
+        } else if self.consume('^') {
+            spec.align = AlignCenter;
         }
         // Sign flags
         if self.consume('+') {
@@ -747,6 +749,18 @@ mod tests {
             },
             method: None,
         })]);
+        same("{3:*^}", ~[Argument(Argument {
+            position: ArgumentIs(3),
+            format: FormatSpec {
+                fill: Some('*'),
+                align: AlignCenter,
+                flags: 0,
+                precision: CountImplied,
+                width: CountImplied,
+                ty: "",
+            },
+            method: None,
+        })]);
         same("{3:*<abcd}", ~[Argument(Argument {
             position: ArgumentIs(3),
             format: FormatSpec {
@@ -9,7 +9,8 @@
 // except according to those terms.
 
 /*!
- * Implementation of SipHash 2-4
+ * Implementation of SipHash 2-4, and of the faster SipHash 1-3 behind the
+ * generic `Hasher` interface.
  *
  * See: http://131002.net/siphash/
  *
@@ -27,6 +28,7 @@
 #[allow(missing_doc)];
 
 use container::Container;
+use default::Default;
 use iter::Iterator;
 use option::{Some, None};
 use io::Writer;
@@ -400,6 +402,188 @@ impl Streaming for SipState {
     }
 }
 
+/// A generic streaming hash function, consuming bytes and producing a
+/// 64-bit hash of everything written so far.
+pub trait Hasher {
+    /// Feed `bytes` into the hash state.
+    fn write(&mut self, bytes: &[u8]);
+
+    /// Return the hash of the bytes written so far. This does not reset
+    /// the state, so more bytes may be written afterwards.
+    fn finish(&self) -> u64;
+}
+
+/**
+ * Types that can be fed into a particular `Hasher`.
+ *
+ * As with `Hash`, every type implementing `IterBytes` gets this for free,
+ * which covers the primitive types, strings, vectors, tuples and so on.
+ */
+pub trait HashWith<H> {
+    /// Feed the bytes of this value into `state`.
+    fn hash_with(&self, state: &mut H);
+}
+
+impl<A: IterBytes, H: Hasher> HashWith<H> for A {
+    #[inline]
+    fn hash_with(&self, state: &mut H) {
+        self.iter_bytes(true, |bytes| {
+            state.write(bytes);
+            true
+        });
+    }
+}
+
+/**
+ * Hash `value` with a fresh, default-constructed `H`.
+ *
+ * # Example
+ *
+ * ~~~ {.rust}
+ * use std::hash::{hash, SipHasher};
+ *
+ * let h = hash::<SipHasher, ~str>(&~"hello");
+ * ~~~
+ */
+#[inline]
+pub fn hash<H: Hasher + Default, T: HashWith<H>>(value: &T) -> u64 {
+    let mut state: H = Default::default();
+    value.hash_with(&mut state);
+    state.finish()
+}
+
+/// An implementation of SipHash 1-3: one compression round per message
+/// block and three finalization rounds. This trades some of the security
+/// margin of SipHash 2-4 for speed on short keys.
+pub struct SipHasher {
+    priv k0: u64,
+    priv k1: u64,
+    priv length: uint, // how many bytes we've processed
+    priv v0: u64,      // hash state
+    priv v1: u64,
+    priv v2: u64,
+    priv v3: u64,
+    priv tail: [u8, ..8], // unprocessed bytes
+    priv ntail: uint,  // how many bytes in tail are valid
+}
+
+impl SipHasher {
+    /// Create a `SipHasher` keyed with zeroes.
+    #[inline]
+    pub fn new() -> SipHasher {
+        SipHasher::new_with_keys(0, 0)
+    }
+
+    /// Create a `SipHasher` keyed with `key0` and `key1`.
+    #[inline]
+    pub fn new_with_keys(key0: u64, key1: u64) -> SipHasher {
+        SipHasher {
+            k0: key0,
+            k1: key1,
+            length: 0,
+            v0: key0 ^ 0x736f6d6570736575,
+            v1: key1 ^ 0x646f72616e646f6d,
+            v2: key0 ^ 0x6c7967656e657261,
+            v3: key1 ^ 0x7465646279746573,
+            tail: [ 0, 0, 0, 0, 0, 0, 0, 0 ],
+            ntail: 0,
+        }
+    }
+}
+
+impl Default for SipHasher {
+    #[inline]
+    fn default() -> SipHasher { SipHasher::new() }
+}
+
+impl Hasher for SipHasher {
+    #[inline]
+    fn write(&mut self, msg: &[u8]) {
+        let length = msg.len();
+        self.length += length;
+
+        let mut needed = 0u;
+
+        if self.ntail != 0 {
+            needed = 8 - self.ntail;
+
+            if length < needed {
+                let mut t = 0;
+                while t < length {
+                    self.tail[self.ntail+t] = msg[t];
+                    t += 1;
+                }
+                self.ntail += length;
+                return;
+            }
+
+            let mut t = 0;
+            while t < needed {
+                self.tail[self.ntail+t] = msg[t];
+                t += 1;
+            }
+
+            let m = u8to64_le!(self.tail, 0);
+
+            self.v3 ^= m;
+            compress!(self.v0, self.v1, self.v2, self.v3);
+            self.v0 ^= m;
+
+            self.ntail = 0;
+        }
+
+        // Buffered tail is now flushed, process new input.
+        let len = length - needed;
+        let end = len & (!0x7);
+        let left = len & 0x7;
+
+        let mut i = needed;
+        while i < needed + end {
+            let mi = u8to64_le!(msg, i);
+
+            self.v3 ^= mi;
+            compress!(self.v0, self.v1, self.v2, self.v3);
+            self.v0 ^= mi;
+
+            i += 8;
+        }
+
+        let mut t = 0u;
+        while t < left {
+            self.tail[t] = msg[i+t];
+            t += 1
+        }
+        self.ntail = left;
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        let mut v0 = self.v0;
+        let mut v1 = self.v1;
+        let mut v2 = self.v2;
+        let mut v3 = self.v3;
+
+        let mut b : u64 = (self.length as u64 & 0xff) << 56;
+
+        let mut t = 0u;
+        while t < self.ntail {
+            b |= self.tail[t] as u64 << (8 * t);
+            t += 1;
+        }
+
+        v3 ^= b;
+        compress!(v0, v1, v2, v3);
+        v0 ^= b;
+
+        v2 ^= 0xff;
+        compress!(v0, v1, v2, v3);
+        compress!(v0, v1, v2, v3);
+        compress!(v0, v1, v2, v3);
+
+        v0 ^ v1 ^ v2 ^ v3
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -520,6 +704,65 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_siphash_1_3() {
+        let vecs : [u64, ..16] = [
+            0xabac0158050fc4dc,
+            0xc9f49bf37d57ca93,
+            0x82cb9b024dc7d44d,
+            0x8bf80ab8e7ddf7fb,
+            0xcf75576088d38328,
+            0xdef9d52f49533b67,
+            0xc50d2b50c59f22a7,
+            0xd3927d989bb11140,
+            0x369095118d299a8e,
+            0x25a48eb36c063de4,
+            0x79de85ee92ff097f,
+            0x70c118c1f94dc352,
+            0x78a384b157b4d9a2,
+            0x306f760c1229ffa7,
+            0x605aa111c0f95d34,
+            0xd320d86d2a519956,
+        ];
+
+        let k0 = 0x_07_06_05_04_03_02_01_00_u64;
+        let k1 = 0x_0f_0e_0d_0c_0b_0a_09_08_u64;
+        let mut buf : ~[u8] = ~[];
+        let mut stream_inc = SipHasher::new_with_keys(k0, k1);
+
+        for t in range(0u, 16) {
+            let mut stream_full = SipHasher::new_with_keys(k0, k1);
+            stream_full.write(buf);
+            assert_eq!(stream_full.finish(), vecs[t]);
+            assert_eq!(stream_inc.finish(), vecs[t]);
+
+            buf.push(t as u8);
+            stream_inc.write([t as u8]);
+        }
+    }
+
+    #[test]
+    fn test_hash_with() {
+        fn sip<T: HashWith<SipHasher>>(t: &T) -> u64 {
+            hash::<SipHasher, T>(t)
+        }
+
+        assert_eq!(sip(&0xdeadbeef_u32), sip(&0xdeadbeef_u32));
+        assert!(sip(&1u8) != sip(&2u8));
+        assert!(sip(&true) != sip(&false));
+        assert_eq!(sip(&~"foo"), sip(&("foo")));
+        assert!(sip(&("aa", "bb")) != sip(&("aabb", "")));
+    }
+
+    #[test]
+    fn test_hash_fn() {
+        let mut state = SipHasher::new_with_keys(0, 0);
+        (~"foo").hash_with(&mut state);
+        assert_eq!(hash::<SipHasher, ~str>(&~"foo"), state.finish());
+        assert!(hash::<SipHasher, ~str>(&~"foo") !=
+                hash::<SipHasher, ~str>(&~"bar"));
+    }
+
     #[test] #[cfg(target_arch = "arm")]
     fn test_hash_uint() {
         let val = 0xdeadbeef_deadbeef_u64;
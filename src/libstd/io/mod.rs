@@ -242,6 +242,7 @@ Out of scope
 
 use cast;
 use container::Container;
+use error::Error;
 use int;
 use iter::Iterator;
 use option::{Option, Some, None};
@@ -340,6 +341,10 @@ impl ToStr for IoError {
     }
 }
 
+impl Error for IoError {
+    fn description<'a>(&'a self) -> &'a str { self.desc }
+}
+
 #[deriving(Eq)]
 pub enum IoErrorKind {
     PreviousIoError,
@@ -17,6 +17,7 @@ use io::IoError;
 use io;
 use libc::c_int;
 use libc;
+use mem::MaybeUninit;
 use ops::Drop;
 use option::{Some, None, Option};
 use os;
@@ -846,10 +847,10 @@ pub fn stat(p: &CString) -> IoResult<io::FileStat> {
 
     #[cfg(windows)]
     fn os_stat(p: &CString) -> IoResult<io::FileStat> {
-        let mut stat: libc::stat = unsafe { intrinsics::uninit() };
+        let mut stat: MaybeUninit<libc::stat> = MaybeUninit::uninit();
         as_utf16_p(p.as_str().unwrap(), |up| {
-            match unsafe { libc::wstat(up, &mut stat) } {
-                0 => Ok(mkstat(&stat, p)),
+            match unsafe { libc::wstat(up, stat.as_mut_ptr()) } {
+                0 => Ok(mkstat(unsafe { &*stat.as_ptr() }, p)),
                 _ => Err(super::last_error()),
             }
         })
@@ -857,9 +858,9 @@ pub fn stat(p: &CString) -> IoResult<io::FileStat> {
 
     #[cfg(unix)]
     fn os_stat(p: &CString) -> IoResult<io::FileStat> {
-        let mut stat: libc::stat = unsafe { intrinsics::uninit() };
-        match unsafe { libc::stat(p.with_ref(|p| p), &mut stat) } {
-            0 => Ok(mkstat(&stat, p)),
+        let mut stat: MaybeUninit<libc::stat> = MaybeUninit::uninit();
+        match unsafe { libc::stat(p.with_ref(|p| p), stat.as_mut_ptr()) } {
+            0 => Ok(mkstat(unsafe { &*stat.as_ptr() }, p)),
             _ => Err(super::last_error()),
         }
     }
@@ -876,9 +877,9 @@ pub fn lstat(p: &CString) -> IoResult<io::FileStat> {
 
     #[cfg(unix)]
     fn os_lstat(p: &CString) -> IoResult<io::FileStat> {
-        let mut stat: libc::stat = unsafe { intrinsics::uninit() };
-        match unsafe { libc::lstat(p.with_ref(|p| p), &mut stat) } {
-            0 => Ok(mkstat(&stat, p)),
+        let mut stat: MaybeUninit<libc::stat> = MaybeUninit::uninit();
+        match unsafe { libc::lstat(p.with_ref(|p| p), stat.as_mut_ptr()) } {
+            0 => Ok(mkstat(unsafe { &*stat.as_ptr() }, p)),
             _ => Err(super::last_error()),
         }
     }
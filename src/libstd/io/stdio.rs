@@ -35,6 +35,7 @@ use rt::rtio::{IoFactory, RtioTTY, RtioFileStream, with_local_io,
                CloseAsynchronously};
 use super::{Reader, Writer, io_error, IoError, OtherIoError,
             standard_error, EndOfFile};
+use unstable::mutex::{Mutex, MUTEX_INIT};
 
 // And so begins the tale of acquiring a uv handle to a stdio stream on all
 // platforms in all situations. Our story begins by splitting the world into two
@@ -188,6 +189,46 @@ pub fn println_args(fmt: &fmt::Arguments) {
     with_task_stdout(|io| fmt::writeln(io, fmt))
 }
 
+// Held while `eprint_args` and `eprintln_args` write a message, by every task
+// on every thread.
+static mut stderr_lock: Mutex = MUTEX_INIT;
+
+// Writes `msg` to stderr while holding `stderr_lock`.
+//
+// The write blocks the thread rather than going through the scheduler: a task
+// descheduled while holding the lock could leave another task on the same
+// thread waiting for it forever. For the same reason errors are ignored rather
+// than raised, as failing here would never release the lock.
+fn write_stderr_locked(msg: &[u8]) {
+    use io::native::file::FileDesc;
+
+    let mut err = FileDesc::new(libc::STDERR_FILENO, false);
+    unsafe {
+        stderr_lock.lock();
+        io_error::cond.trap(|_| ()).inside(|| err.write(msg));
+        stderr_lock.unlock();
+    }
+}
+
+/// Similar to `print_args`, but writes to the stderr of the current process.
+///
+/// The message is formatted up front and written under a lock, so messages
+/// from concurrent tasks are never interleaved with one another. Other
+/// writers to stderr, such as the logging macros, do not take the lock.
+pub fn eprint_args(fmt: &fmt::Arguments) {
+    let msg = fmt::format(fmt);
+    write_stderr_locked(msg.as_bytes());
+}
+
+/// Similar to `println_args`, but writes to the stderr of the current process.
+///
+/// See `eprint_args` for notes about this function.
+pub fn eprintln_args(fmt: &fmt::Arguments) {
+    let mut msg = fmt::format(fmt);
+    msg.push_char('\n');
+    write_stderr_locked(msg.as_bytes());
+}
+
 /// Representation of a reader of a standard input stream
 pub struct StdReader {
     priv inner: StdSource
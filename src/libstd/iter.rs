@@ -65,13 +65,18 @@ the rest of the rust manuals.
 */
 
 use cmp;
+use container::Container;
+use default::Default;
 use num::{Zero, One, Integer, CheckedAdd, CheckedSub, Saturating, ToPrimitive};
 use option::{Option, Some, None};
 use ops::{Add, Mul, Sub};
+use result::{Result, Ok, Err};
 use cmp::{Eq, Ord};
 use clone::Clone;
 use uint;
 use util;
+use vec::OwnedVector;
+use vec;
 
 /// Conversion from an `Iterator`
 pub trait FromIterator<A> {
@@ -234,6 +239,37 @@ pub trait Iterator<A> {
         Peekable{iter: self, peeked: None}
     }
 
+    /// Creates an iterator which yields a clone of `separator` between each
+    /// pair of adjacent elements of this iterator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let a = [1, 2, 3];
+    /// let v: ~[&int] = a.iter().intersperse(&0).collect();
+    /// assert_eq!(v, ~[&1, &0, &2, &0, &3]);
+    /// ```
+    #[inline]
+    fn intersperse(self, separator: A) -> Intersperse<A, Self> {
+        Intersperse{iter: self.peekable(), separator: separator, needs_sep: false}
+    }
+
+    /// Creates an iterator which yields the result of calling `separator`
+    /// between each pair of adjacent elements of this iterator, for elements
+    /// which cannot be cloned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let a = [~"a", ~"b"];
+    /// let v: ~[~str] = a.iter().map(|s| s.clone()).intersperse_with(|| ~",").collect();
+    /// assert_eq!(v, ~[~"a", ~",", ~"b"]);
+    /// ```
+    #[inline]
+    fn intersperse_with<'r>(self, separator: 'r || -> A) -> IntersperseWith<'r, A, Self> {
+        IntersperseWith{iter: self.peekable(), separator: separator, needs_sep: false}
+    }
+
     /// Creates an iterator which invokes the predicate on elements until it
     /// returns false. Once the predicate returns false, all further elements are
     /// yielded.
@@ -288,6 +324,49 @@ pub trait Iterator<A> {
         Skip{iter: self, n: n}
     }
 
+    /// Creates an iterator which yields the elements of this iterator in
+    /// vectors of exactly `size` elements. Any elements left over at the end
+    /// are kept back, and can be recovered with `into_remainder`.
+    ///
+    /// Fails if `size` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let a = [1, 2, 3, 4, 5];
+    /// let mut it = a.iter().map(|&x| x).array_chunks(2);
+    /// assert_eq!(it.next().unwrap(), ~[1, 2]);
+    /// assert_eq!(it.next().unwrap(), ~[3, 4]);
+    /// assert!(it.next().is_none());
+    /// assert_eq!(it.into_remainder(), ~[5]);
+    /// ```
+    #[inline]
+    fn array_chunks(self, size: uint) -> ArrayChunks<A, Self> {
+        assert!(size != 0, "array_chunks: size must not be zero");
+        ArrayChunks{iter: self, size: size, remainder: ~[]}
+    }
+
+    /// Creates an iterator which yields the first element of this iterator
+    /// and then every `step`th element after it.
+    ///
+    /// Fails if `step` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let a = [1, 2, 3, 4, 5];
+    /// let mut it = a.iter().step_by(2);
+    /// assert_eq!(it.next().unwrap(), &1);
+    /// assert_eq!(it.next().unwrap(), &3);
+    /// assert_eq!(it.next().unwrap(), &5);
+    /// assert!(it.next().is_none());
+    /// ```
+    #[inline]
+    fn step_by(self, step: uint) -> StepBy<Self> {
+        assert!(step != 0, "step_by: step must not be zero");
+        StepBy{iter: self, step: step, first: true}
+    }
+
     /// Creates an iterator which yields the first `n` elements of this
     /// iterator, and then it will always return None.
     ///
@@ -533,6 +612,34 @@ pub trait Iterator<A> {
         accum
     }
 
+    /// Performs a fold operation which can fail, stopping at the first `Err`
+    /// returned by `f` and returning it. Otherwise returns the final state
+    /// wrapped in `Ok`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let a = [1, 2, 3, 4, 5];
+    /// let r = a.iter().try_fold(0, |acc, &x| if x < 4 { Ok(acc + x) } else { Err(x) });
+    /// assert!(r == Err(4));
+    /// ```
+    #[inline]
+    fn try_fold<B, E>(&mut self, init: B, f: |B, A| -> Result<B, E>) -> Result<B, E> {
+        let mut accum = init;
+        loop {
+            match self.next() {
+                Some(x) => {
+                    match f(accum, x) {
+                        Ok(b) => accum = b,
+                        Err(e) => return Err(e)
+                    }
+                }
+                None => { break; }
+            }
+        }
+        Ok(accum)
+    }
+
     /// Counts the number of elements in this iterator.
     ///
     /// # Example
@@ -682,16 +789,45 @@ pub trait DoubleEndedIterator<A>: Iterator<A> {
     fn invert(self) -> Invert<Self> {
         Invert{iter: self}
     }
+
+    /// Flip the direction of the iterator. This is the same as `invert`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let a = [1, 2, 3];
+    /// let v: ~[&int] = a.iter().rev().collect();
+    /// assert_eq!(v, ~[&3, &2, &1]);
+    /// ```
+    #[inline]
+    fn rev(self) -> Rev<Self> {
+        self.invert()
+    }
 }
 
 /// A double-ended iterator yielding mutable references
-pub trait MutableDoubleEndedIterator {
+pub trait MutableDoubleEndedIterator<A> {
     // FIXME: #5898: should be called `reverse`
     /// Use an iterator to reverse a container in-place
     fn reverse_(&mut self);
+
+    /// Reorders the elements in place so that those satisfying `predicate`
+    /// come before those that don't, and returns how many satisfy it. The
+    /// order within each part is not preserved.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut a = [1, 2, 3, 4, 5, 6];
+    /// let n = a.mut_iter().partition_in_place(|&x| x % 2 == 0);
+    /// assert_eq!(n, 3);
+    /// assert!(a.slice_to(n).iter().all(|&x| x % 2 == 0));
+    /// assert!(a.slice_from(n).iter().all(|&x| x % 2 == 1));
+    /// ```
+    fn partition_in_place(&mut self, predicate: |&A| -> bool) -> uint;
 }
 
-impl<'self, A, T: DoubleEndedIterator<&'self mut A>> MutableDoubleEndedIterator for T {
+impl<'self, A, T: DoubleEndedIterator<&'self mut A>> MutableDoubleEndedIterator<A> for T {
     // FIXME: #5898: should be called `reverse`
     /// Use an iterator to reverse a container in-place
     fn reverse_(&mut self) {
@@ -702,6 +838,41 @@ impl<'self, A, T: DoubleEndedIterator<&'self mut A>> MutableDoubleEndedIterator
             }
         }
     }
+
+    fn partition_in_place(&mut self, predicate: |&A| -> bool) -> uint {
+        let mut count = 0;
+        loop {
+            // Find the first element from the front which belongs at the
+            // back, and the first from the back which belongs at the front.
+            let mut front = None;
+            loop {
+                match self.next() {
+                    Some(x) => {
+                        if !predicate(&*x) { front = Some(x); break; }
+                        count += 1;
+                    }
+                    None => break
+                }
+            }
+            let front = match front {
+                Some(x) => x,
+                None => return count
+            };
+            let mut back = None;
+            loop {
+                match self.next_back() {
+                    Some(y) => {
+                        if predicate(&*y) { back = Some(y); break; }
+                    }
+                    None => break
+                }
+            }
+            match back {
+                Some(y) => { util::swap(front, y); count += 1; }
+                None => return count
+            }
+        }
+    }
 }
 
 
@@ -725,6 +896,28 @@ pub trait RandomAccessIterator<A>: Iterator<A> {
 /// `Iterator::size_hint` *must* return the exact size of the iterator.
 /// Note that the size must fit in `uint`.
 pub trait ExactSize<A> : DoubleEndedIterator<A> {
+    /// Return the exact number of elements left in the iterator, without
+    /// consuming any of them.
+    ///
+    /// This is not named `len` because `Iterator::len` already counts the
+    /// elements by consuming the iterator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let a = [1, 2, 3, 4, 5];
+    /// let mut it = a.iter();
+    /// assert_eq!(it.exact_len(), 5);
+    /// it.next();
+    /// assert_eq!(it.exact_len(), 4);
+    /// ```
+    #[inline]
+    fn exact_len(&self) -> uint {
+        let (lower, upper) = self.size_hint();
+        assert!(upper == Some(lower));
+        lower
+    }
+
     /// Return the index of the last element satisfying the specified predicate
     ///
     /// If no element matches, None is returned.
@@ -751,13 +944,43 @@ pub trait ExactSize<A> : DoubleEndedIterator<A> {
     }
 }
 
-// All adaptors that preserve the size of the wrapped iterator are fine
-// Adaptors that may overflow in `size_hint` are not, i.e. `Chain`.
+// All adaptors that preserve the size of the wrapped iterator are fine.
+// `Chain` may overflow in `size_hint`, in which case `exact_len` fails.
 impl<A, T: ExactSize<A>> ExactSize<(uint, A)> for Enumerate<T> {}
 impl<'self, A, T: ExactSize<A>> ExactSize<A> for Inspect<'self, A, T> {}
 impl<A, T: ExactSize<A>> ExactSize<A> for Invert<T> {}
 impl<'self, A, B, T: ExactSize<A>> ExactSize<B> for Map<'self, A, B, T> {}
 impl<A, B, T: ExactSize<A>, U: ExactSize<B>> ExactSize<(A, B)> for Zip<T, U> {}
+impl<A, T: ExactSize<A>> ExactSize<A> for Peekable<A, T> {}
+impl<A, T: ExactSize<A>> ExactSize<A> for Skip<T> {}
+impl<A, T: ExactSize<A>> ExactSize<A> for Take<T> {}
+impl<A, T: ExactSize<A>> ExactSize<A> for Fuse<T> {}
+impl<A, T: ExactSize<A>, U: ExactSize<A>> ExactSize<A> for Chain<T, U> {}
+impl<A: Integer + Ord + Clone + ToPrimitive> ExactSize<A> for Range<A> {}
+
+/// A marker for iterators which keep returning `None` once they have
+/// returned it. Any iterator can be made to behave this way with `fuse`.
+pub trait FusedIterator<A>: Iterator<A> {}
+
+impl<A, T: Iterator<A>> FusedIterator<A> for Fuse<T> {}
+impl<A: Add<A, A> + Clone> FusedIterator<A> for Counter<A> {}
+impl<A: Add<A, A> + Ord + Clone + ToPrimitive> FusedIterator<A> for Range<A> {}
+impl<A: Add<A, A> + Eq + Ord + Clone + ToPrimitive> FusedIterator<A> for RangeInclusive<A> {}
+impl<A: Clone> FusedIterator<A> for Repeat<A> {}
+
+// Adaptors are fused when the iterators they wrap are.
+impl<A, T: FusedIterator<A>, U: FusedIterator<A>> FusedIterator<A> for Chain<T, U> {}
+impl<A, B, T: FusedIterator<A>, U: FusedIterator<B>> FusedIterator<(A, B)> for Zip<T, U> {}
+impl<'self, A, B, T: FusedIterator<A>> FusedIterator<B> for Map<'self, A, B, T> {}
+impl<'self, A, T: FusedIterator<A>> FusedIterator<A> for Filter<'self, A, T> {}
+impl<'self, A, B, T: FusedIterator<A>> FusedIterator<B> for FilterMap<'self, A, B, T> {}
+impl<A, T: FusedIterator<A>> FusedIterator<(uint, A)> for Enumerate<T> {}
+impl<A, T: FusedIterator<A>> FusedIterator<A> for Peekable<A, T> {}
+impl<'self, A, T: FusedIterator<A>> FusedIterator<A> for SkipWhile<'self, A, T> {}
+impl<'self, A, T: FusedIterator<A>> FusedIterator<A> for TakeWhile<'self, A, T> {}
+impl<A, T: FusedIterator<A>> FusedIterator<A> for Skip<T> {}
+impl<A, T: FusedIterator<A>> FusedIterator<A> for Take<T> {}
+impl<'self, A, T: FusedIterator<A>> FusedIterator<A> for Inspect<'self, A, T> {}
 
 /// An double-ended iterator with the direction inverted
 #[deriving(Clone)]
@@ -765,6 +988,9 @@ pub struct Invert<T> {
     priv iter: T
 }
 
+/// The iterator returned by `DoubleEndedIterator::rev`
+pub type Rev<T> = Invert<T>;
+
 impl<A, T: DoubleEndedIterator<A>> Iterator<A> for Invert<T> {
     #[inline]
     fn next(&mut self) -> Option<A> { self.iter.next_back() }
@@ -875,6 +1101,22 @@ pub trait OrdIterator<A> {
     /// assert!(a.iter().min().unwrap() == &1);
     /// ```
     fn min(&mut self) -> Option<A>;
+
+    /// Consumes the entire iterator to return both the minimum and the
+    /// maximum element, using about 3n/2 comparisons rather than the 2n of
+    /// separate `min` and `max` calls. Of several equal elements, the first
+    /// is the minimum and the last is the maximum.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::iter::{OneElement, MinMax};
+    ///
+    /// let a = [1, 2, 3, 4, 5];
+    /// assert!(a.iter().min_max() == MinMax(&1, &5));
+    /// assert!([1].iter().min_max() == OneElement(&1));
+    /// ```
+    fn min_max(&mut self) -> MinMaxResult<A>;
 }
 
 impl<A: Ord, T: Iterator<A>> OrdIterator<A> for T {
@@ -897,6 +1139,69 @@ impl<A: Ord, T: Iterator<A>> OrdIterator<A> for T {
             }
         })
     }
+
+    fn min_max(&mut self) -> MinMaxResult<A> {
+        let (mut min, mut max) = match self.next() {
+            None => return NoElements,
+            Some(x) => {
+                match self.next() {
+                    None => return OneElement(x),
+                    Some(y) => if y < x { (y, x) } else { (x, y) }
+                }
+            }
+        };
+
+        // Compare the elements with each other in pairs, and then only the
+        // smaller with `min` and the larger with `max`.
+        loop {
+            let first = match self.next() {
+                None => break,
+                Some(x) => x
+            };
+            let second = match self.next() {
+                None => {
+                    if first < min {
+                        min = first;
+                    } else if first >= max {
+                        max = first;
+                    }
+                    break;
+                }
+                Some(x) => x
+            };
+            if second < first {
+                if second < min { min = second; }
+                if first >= max { max = first; }
+            } else {
+                if first < min { min = first; }
+                if second >= max { max = second; }
+            }
+        }
+        MinMax(min, max)
+    }
+}
+
+/// The result of `OrdIterator::min_max`.
+#[deriving(Clone, Eq)]
+pub enum MinMaxResult<T> {
+    /// The iterator was empty
+    NoElements,
+    /// The iterator had a single element, which is both the minimum and the
+    /// maximum
+    OneElement(T),
+    /// The minimum and the maximum of an iterator with several elements
+    MinMax(T, T)
+}
+
+impl<T: Clone> MinMaxResult<T> {
+    /// Returns the minimum and the maximum, if there are any elements.
+    pub fn into_option(self) -> Option<(T, T)> {
+        match self {
+            NoElements => None,
+            OneElement(x) => Some((x.clone(), x)),
+            MinMax(x, y) => Some((x, y))
+        }
+    }
 }
 
 /// A trait for iterators that are clonable.
@@ -1340,6 +1645,16 @@ impl<A, T: Iterator<A>> Iterator<A> for Peekable<A, T> {
     }
 }
 
+impl<A, T: DoubleEndedIterator<A>> DoubleEndedIterator<A> for Peekable<A, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<A> {
+        match self.iter.next_back() {
+            None => self.peeked.take(),
+            next => next
+        }
+    }
+}
+
 impl<'self, A, T: Iterator<A>> Peekable<A, T> {
     /// Return a reference to the next element of the iterator with out advancing it,
     /// or None if the iterator is exhausted.
@@ -1355,6 +1670,164 @@ impl<'self, A, T: Iterator<A>> Peekable<A, T> {
     }
 }
 
+/// An iterator which yields the elements of `iter` in vectors of `size`
+/// elements.
+pub struct ArrayChunks<A, T> {
+    priv iter: T,
+    priv size: uint,
+    priv remainder: ~[A]
+}
+
+impl<A, T: Iterator<A>> Iterator<~[A]> for ArrayChunks<A, T> {
+    #[inline]
+    fn next(&mut self) -> Option<~[A]> {
+        let mut chunk = vec::with_capacity(self.size);
+        while chunk.len() < self.size {
+            match self.iter.next() {
+                Some(x) => chunk.push(x),
+                None => {
+                    self.remainder.push_all_move(chunk);
+                    return None;
+                }
+            }
+        }
+        Some(chunk)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (uint, Option<uint>) {
+        let (lower, upper) = self.iter.size_hint();
+        let upper = match upper {
+            Some(x) => Some(x / self.size),
+            None => None
+        };
+        (lower / self.size, upper)
+    }
+}
+
+impl<A, T> ArrayChunks<A, T> {
+    /// Consumes the iterator, returning the elements which did not fill a
+    /// whole chunk. This is only complete once `next` has returned `None`.
+    #[inline]
+    pub fn into_remainder(self) -> ~[A] {
+        self.remainder
+    }
+}
+
+/// An iterator which yields every `step`th element of `iter`.
+#[deriving(Clone)]
+pub struct StepBy<T> {
+    priv iter: T,
+    priv step: uint,
+    priv first: bool
+}
+
+impl<A, T: Iterator<A>> Iterator<A> for StepBy<T> {
+    #[inline]
+    fn next(&mut self) -> Option<A> {
+        if self.first {
+            self.first = false;
+            return self.iter.next();
+        }
+        let mut n = self.step - 1;
+        while n > 0 {
+            n -= 1;
+            if self.iter.next().is_none() {
+                return None;
+            }
+        }
+        self.iter.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (uint, Option<uint>) {
+        let (lower, upper) = self.iter.size_hint();
+        let step = self.step;
+        let first = self.first;
+        // How many of `n` remaining elements will be yielded.
+        let steps = |n: uint| if first {
+            if n == 0 { 0 } else { 1 + (n - 1) / step }
+        } else {
+            n / step
+        };
+        let upper = match upper {
+            Some(x) => Some(steps(x)),
+            None => None
+        };
+        (steps(lower), upper)
+    }
+}
+
+// The number of elements yielded when a separator goes between each of `n`
+// elements, and before the first if `needs_sep` is set.
+fn interspersed_len(n: uint, needs_sep: bool) -> Option<uint> {
+    match n.checked_add(&n) {
+        Some(m) if !needs_sep && m > 0 => Some(m - 1),
+        m => m
+    }
+}
+
+fn interspersed_size_hint(hint: (uint, Option<uint>), needs_sep: bool)
+                          -> (uint, Option<uint>) {
+    let (lo, hi) = hint;
+    let lo = interspersed_len(lo, needs_sep).unwrap_or(uint::max_value);
+    (lo, hi.and_then(|n| interspersed_len(n, needs_sep)))
+}
+
+/// An iterator which yields a clone of `separator` between the elements of
+/// `iter`.
+pub struct Intersperse<A, T> {
+    priv iter: Peekable<A, T>,
+    priv separator: A,
+    priv needs_sep: bool
+}
+
+impl<A: Clone, T: Iterator<A>> Iterator<A> for Intersperse<A, T> {
+    #[inline]
+    fn next(&mut self) -> Option<A> {
+        if self.needs_sep && self.iter.peek().is_some() {
+            self.needs_sep = false;
+            Some(self.separator.clone())
+        } else {
+            let next = self.iter.next();
+            self.needs_sep = next.is_some();
+            next
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (uint, Option<uint>) {
+        interspersed_size_hint(self.iter.size_hint(), self.needs_sep)
+    }
+}
+
+/// An iterator which yields the result of calling `separator` between the
+/// elements of `iter`.
+pub struct IntersperseWith<'self, A, T> {
+    priv iter: Peekable<A, T>,
+    priv separator: 'self || -> A,
+    priv needs_sep: bool
+}
+
+impl<'self, A, T: Iterator<A>> Iterator<A> for IntersperseWith<'self, A, T> {
+    #[inline]
+    fn next(&mut self) -> Option<A> {
+        if self.needs_sep && self.iter.peek().is_some() {
+            self.needs_sep = false;
+            Some((self.separator)())
+        } else {
+            let next = self.iter.next();
+            self.needs_sep = next.is_some();
+            next
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (uint, Option<uint>) {
+        interspersed_size_hint(self.iter.size_hint(), self.needs_sep)
+    }
+}
+
 /// An iterator which rejects elements while `predicate` is true
 pub struct SkipWhile<'self, A, T> {
     priv iter: T,
@@ -1475,6 +1948,18 @@ impl<A, T: Iterator<A>> Iterator<A> for Skip<T> {
     }
 }
 
+impl<A, T: ExactSize<A>> DoubleEndedIterator<A> for Skip<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<A> {
+        let (len, _) = self.iter.size_hint();
+        if len > self.n {
+            self.iter.next_back()
+        } else {
+            None
+        }
+    }
+}
+
 impl<A, T: RandomAccessIterator<A>> RandomAccessIterator<A> for Skip<T> {
     #[inline]
     fn indexable(&self) -> uint {
@@ -1524,6 +2009,24 @@ impl<A, T: Iterator<A>> Iterator<A> for Take<T> {
     }
 }
 
+impl<A, T: ExactSize<A>> DoubleEndedIterator<A> for Take<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<A> {
+        if self.n == 0 {
+            return None;
+        }
+        // Drop the elements at the back which are beyond the first `n`.
+        let (len, _) = self.iter.size_hint();
+        let mut extra = len.saturating_sub(self.n);
+        while extra > 0 {
+            self.iter.next_back();
+            extra -= 1;
+        }
+        self.n -= 1;
+        self.iter.next_back()
+    }
+}
+
 impl<A, T: RandomAccessIterator<A>> RandomAccessIterator<A> for Take<T> {
     #[inline]
     fn indexable(&self) -> uint {
@@ -1622,6 +2125,120 @@ impl<'self,
     }
 }
 
+/// A trait for iterators over pairs, which can be split into two collections
+pub trait UnzipIterator<A, B> {
+    /// Consumes the iterator, collecting the first elements of the pairs
+    /// into one collection and the second elements into another. This is
+    /// the inverse of `zip`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::iter::UnzipIterator;
+    ///
+    /// let a = [(1, 'a'), (2, 'b')];
+    /// let (xs, cs): (~[int], ~str) = a.iter().map(|&x| x).unzip();
+    /// assert!(xs == ~[1, 2]);
+    /// assert!(cs == ~"ab");
+    /// ```
+    fn unzip<FromA: Default + Extendable<A>, FromB: Default + Extendable<B>>(self)
+        -> (FromA, FromB);
+}
+
+impl<A, B, T: Iterator<(A, B)>> UnzipIterator<A, B> for T {
+    #[inline]
+    fn unzip<FromA: Default + Extendable<A>, FromB: Default + Extendable<B>>(self)
+        -> (FromA, FromB) {
+        let mut it = self;
+        let mut a: FromA = Default::default();
+        let mut b: FromB = Default::default();
+        for (x, y) in it {
+            a.extend(&mut Some(x).move_iter());
+            b.extend(&mut Some(y).move_iter());
+        }
+        (a, b)
+    }
+}
+
+/// A trait for iterators over iterators, which can be flattened into one
+pub trait FlattenIterator<U> {
+    /// Creates an iterator which yields the elements of each inner iterator
+    /// in turn.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::iter::FlattenIterator;
+    ///
+    /// let a = [~[1, 2], ~[], ~[3]];
+    /// let v: ~[&int] = a.iter().map(|v| v.iter()).flatten().collect();
+    /// assert!(v == ~[&1, &2, &3]);
+    /// ```
+    fn flatten(self) -> Flatten<Self, U>;
+}
+
+impl<U, T: Iterator<U>> FlattenIterator<U> for T {
+    #[inline]
+    fn flatten(self) -> Flatten<T, U> {
+        Flatten{iter: self, frontiter: None, backiter: None}
+    }
+}
+
+/// An iterator that yields the elements of each iterator produced by
+/// another iterator
+pub struct Flatten<T, U> {
+    priv iter: T,
+    priv frontiter: Option<U>,
+    priv backiter: Option<U>,
+}
+
+impl<B, U: Iterator<B>, T: Iterator<U>> Iterator<B> for Flatten<T, U> {
+    #[inline]
+    fn next(&mut self) -> Option<B> {
+        loop {
+            for inner in self.frontiter.mut_iter() {
+                for x in *inner {
+                    return Some(x)
+                }
+            }
+            match self.iter.next() {
+                None => return self.backiter.as_mut().and_then(|it| it.next()),
+                next => self.frontiter = next,
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (uint, Option<uint>) {
+        let (flo, fhi) = self.frontiter.as_ref().map_default((0, Some(0)), |it| it.size_hint());
+        let (blo, bhi) = self.backiter.as_ref().map_default((0, Some(0)), |it| it.size_hint());
+        let lo = flo.saturating_add(blo);
+        match (self.iter.size_hint(), fhi, bhi) {
+            ((0, Some(0)), Some(a), Some(b)) => (lo, a.checked_add(&b)),
+            _ => (lo, None)
+        }
+    }
+}
+
+impl<B, U: DoubleEndedIterator<B>, T: DoubleEndedIterator<U>> DoubleEndedIterator<B>
+    for Flatten<T, U> {
+    #[inline]
+    fn next_back(&mut self) -> Option<B> {
+        loop {
+            for inner in self.backiter.mut_iter() {
+                match inner.next_back() {
+                    None => (),
+                    y => return y
+                }
+            }
+            match self.iter.next_back() {
+                None => return self.frontiter.as_mut().and_then(|it| it.next_back()),
+                next => self.backiter = next,
+            }
+        }
+    }
+}
+
 /// An iterator that yields `None` forever after the underlying iterator
 /// yields `None` once.
 #[deriving(Clone, DeepClone)]
@@ -1848,6 +2465,8 @@ impl<A: Add<A, A> + Ord + Clone + ToPrimitive> Iterator<A> for Range<A> {
             Some(a) => {
                 let sz = self.stop.to_i64().map(|b| b.checked_sub(&a));
                 match sz {
+                    // An empty range must still report an exact size
+                    Some(Some(bound)) if bound < 0 => Some(0),
                     Some(Some(bound)) => bound.to_uint(),
                     _ => None,
                 }
@@ -1856,6 +2475,7 @@ impl<A: Add<A, A> + Ord + Clone + ToPrimitive> Iterator<A> for Range<A> {
                 Some(a) => {
                     let sz = self.stop.to_u64().map(|b| b.checked_sub(&a));
                     match sz {
+                        Some(None) => Some(0),
                         Some(Some(bound)) => bound.to_uint(),
                         _ => None
                     }
@@ -2214,6 +2834,7 @@ mod tests {
     use cmp;
     use uint;
     use num;
+    use ptr;
 
     #[test]
     fn test_counter_from_iter() {
@@ -2279,6 +2900,82 @@ mod tests {
         assert!(it.next().is_none());
     }
 
+    #[test]
+    fn test_iterator_peekable_double_ended() {
+        let xs = [0u, 1, 2, 3];
+        let mut it = xs.iter().peekable();
+        assert_eq!(it.peek(), Some(&&0));
+        assert_eq!(it.size_hint(), (4, Some(4)));
+        assert_eq!(it.next_back(), Some(&3));
+        assert_eq!(it.rposition(|&x| x == 1), Some(1));
+
+        let mut it = xs.slice_to(1).iter().peekable();
+        assert_eq!(it.peek(), Some(&&0));
+        assert_eq!(it.next_back(), Some(&0));
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn test_iterator_array_chunks() {
+        let mut it = range(0, 8).array_chunks(3);
+        assert_eq!(it.size_hint(), (2, Some(2)));
+        assert_eq!(it.next(), Some(~[0, 1, 2]));
+        assert_eq!(it.next(), Some(~[3, 4, 5]));
+        assert_eq!(it.next(), None);
+        assert_eq!(it.into_remainder(), ~[6, 7]);
+
+        let mut it = range(0, 4).array_chunks(2);
+        assert_eq!(it.len(), 2);
+        assert!(it.into_remainder().is_empty());
+    }
+
+    #[test]
+    fn test_iterator_step_by() {
+        let v: ~[uint] = range(0u, 100).step_by(3).collect();
+        assert_eq!(v.len(), 34);
+        assert_eq!(v[1], 3);
+        assert_eq!(v[33], 99);
+
+        let mut it = range(0u, 10).step_by(4);
+        assert_eq!(it.size_hint(), (3, Some(3)));
+        assert_eq!(it.next(), Some(0));
+        assert_eq!(it.size_hint(), (2, Some(2)));
+        assert_eq!(it.next(), Some(4));
+        assert_eq!(it.next(), Some(8));
+        assert_eq!(it.size_hint(), (0, Some(0)));
+        assert_eq!(it.next(), None);
+
+        let mut it = count(0u, 1).step_by(1);
+        assert_eq!(it.size_hint(), (uint::max_value, None));
+        assert_eq!(it.nth(5), Some(5));
+    }
+
+    #[test]
+    #[should_fail]
+    fn test_iterator_step_by_zero() {
+        range(0u, 10).step_by(0);
+    }
+
+    #[test]
+    fn test_iterator_intersperse() {
+        let xs = [1, 2, 3];
+        let mut it = xs.iter().intersperse(&0);
+        assert_eq!(it.size_hint(), (5, Some(5)));
+        assert_eq!(it.next(), Some(&1));
+        assert_eq!(it.size_hint(), (4, Some(4)));
+        let rest: ~[&int] = it.collect();
+        assert_eq!(rest, ~[&0, &2, &0, &3]);
+
+        let empty: ~[&int] = xs.slice_to(0).iter().intersperse(&0).collect();
+        assert!(empty.is_empty());
+        let one: ~[&int] = xs.slice_to(1).iter().intersperse(&0).collect();
+        assert_eq!(one, ~[&1]);
+
+        let mut n = 0;
+        let v: ~[int] = xs.iter().map(|&x| x).intersperse_with(|| { n -= 1; n }).collect();
+        assert_eq!(v, ~[1, -1, 2, -2, 3]);
+    }
+
     #[test]
     fn test_iterator_take_while() {
         let xs = [0u, 1, 2, 3, 5, 13, 15, 16, 17, 19];
@@ -2350,6 +3047,17 @@ mod tests {
         assert_eq!(i, ys.len());
     }
 
+    #[test]
+    fn test_iterator_scan_stops() {
+        let xs = [1, 2, 3, 4, 5];
+        let mut it = xs.iter().scan(0, |sum, &x| {
+            *sum += x;
+            if *sum < 7 { Some(*sum) } else { None }
+        });
+        let ys: ~[int] = it.collect();
+        assert_eq!(ys, ~[1, 3, 6]);
+    }
+
     #[test]
     fn test_iterator_flat_map() {
         let xs = [0u, 3, 6];
@@ -2363,6 +3071,39 @@ mod tests {
         assert_eq!(i, ys.len());
     }
 
+    #[test]
+    fn test_iterator_unzip() {
+        let xs = [(1, 'a'), (2, 'b'), (3, 'c')];
+        let (ns, cs): (~[int], ~str) = xs.iter().map(|&x| x).unzip();
+        assert_eq!(ns, ~[1, 2, 3]);
+        assert_eq!(cs, ~"abc");
+
+        let (a, b): (~[int], ~[int]) = range(0, 3).zip(range(10, 13)).unzip();
+        assert_eq!(a, ~[0, 1, 2]);
+        assert_eq!(b, ~[10, 11, 12]);
+
+        let empty: ~[(int, int)] = ~[];
+        let (a, b): (~[int], ~[int]) = empty.move_iter().unzip();
+        assert!(a.is_empty() && b.is_empty());
+    }
+
+    #[test]
+    fn test_iterator_flatten() {
+        let xs = [~[0u, 1, 2], ~[], ~[3, 4]];
+        let mut it = xs.iter().map(|v| v.iter()).flatten();
+        assert_eq!(it.size_hint(), (0, None));
+        assert_eq!(it.next(), Some(&0));
+        assert_eq!(it.size_hint(), (2, None));
+        let rest: ~[&uint] = it.collect();
+        assert_eq!(rest, ~[&1, &2, &3, &4]);
+
+        let mut it = xs.iter().map(|v| v.iter()).flatten();
+        assert_eq!(it.next_back(), Some(&4));
+        assert_eq!(it.next(), Some(&0));
+        let rest: ~[&uint] = it.invert().collect();
+        assert_eq!(rest, ~[&3, &2, &1]);
+    }
+
     #[test]
     fn test_inspect() {
         let xs = [1u, 2, 3, 4];
@@ -2443,6 +3184,18 @@ mod tests {
         assert_eq!(v.slice(0, 0).iter().map(|&x| x).sum(), 0);
     }
 
+    #[test]
+    fn test_iterator_try_fold() {
+        let v = &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+        let r: Result<int, int> = v.iter().try_fold(0, |a, &x| Ok(a + x));
+        assert_eq!(r, Ok(55));
+
+        let mut it = v.iter();
+        let r = it.try_fold(0, |a, &x| if x < 3 { Ok(a + x) } else { Err(a) });
+        assert_eq!(r, Err(3));
+        assert_eq!(it.next(), Some(&4));
+    }
+
     #[test]
     fn test_iterator_product() {
         let v = &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
@@ -2467,6 +3220,29 @@ mod tests {
         assert_eq!(v.slice(0, 0).iter().map(|&x| x).min(), None);
     }
 
+    #[test]
+    fn test_iterator_min_max() {
+        let v = &[4, 2, 8, 6, 0, 10, 3];
+        assert_eq!(v.iter().map(|&x| x).min_max(), MinMax(0, 10));
+        assert_eq!(v.slice(0, 4).iter().map(|&x| x).min_max(), MinMax(2, 8));
+        assert_eq!(v.slice(0, 2).iter().map(|&x| x).min_max(), MinMax(2, 4));
+        assert_eq!(v.slice(0, 1).iter().map(|&x| x).min_max(), OneElement(4));
+        assert_eq!(v.slice(0, 0).iter().map(|&x| x).min_max(), NoElements);
+
+        assert_eq!(v.slice(0, 1).iter().map(|&x| x).min_max().into_option(), Some((4, 4)));
+        assert_eq!(v.slice(0, 0).iter().map(|&x| x).min_max().into_option(), None);
+
+        // The first of equal minimums and the last of equal maximums.
+        let w = &[1, 1, 1, 1, 1];
+        match w.iter().min_max() {
+            MinMax(min, max) => {
+                assert!(ptr::to_unsafe_ptr(min) == ptr::to_unsafe_ptr(&w[0]));
+                assert!(ptr::to_unsafe_ptr(max) == ptr::to_unsafe_ptr(&w[4]));
+            }
+            _ => fail!()
+        }
+    }
+
     #[test]
     fn test_iterator_size_hint() {
         let c = count(0, 1);
@@ -2704,6 +3480,84 @@ mod tests {
     }
 
 
+    #[test]
+    fn test_rev() {
+        let xs = [1, 2, 3, 4];
+        let v: ~[&int] = xs.iter().rev().collect();
+        assert_eq!(v, ~[&4, &3, &2, &1]);
+        let mut it = xs.iter().rev();
+        assert_eq!(it.size_hint(), (4, Some(4)));
+        assert_eq!(it.rposition(|&x| x == 3), Some(1));
+    }
+
+    #[test]
+    fn test_exact_len() {
+        let xs = [1, 2, 3, 4];
+        let ys = [5, 6];
+
+        let mut it = xs.iter();
+        assert_eq!(it.exact_len(), 4);
+        it.next();
+        it.next_back();
+        assert_eq!(it.exact_len(), 2);
+
+        assert_eq!(xs.iter().enumerate().exact_len(), 4);
+        assert_eq!(xs.iter().zip(ys.iter()).exact_len(), 2);
+        assert_eq!(xs.iter().chain(ys.iter()).exact_len(), 6);
+        assert_eq!(range(3u, 10).exact_len(), 7);
+        assert_eq!(range(10u, 3).exact_len(), 0);
+
+        let mut it = xs.iter().chain(ys.iter());
+        for _ in range(0, 5) { it.next(); }
+        assert_eq!(it.exact_len(), 1);
+    }
+
+    #[test]
+    fn test_double_ended_skip_take() {
+        let xs = [0, 1, 2, 3, 4, 5];
+        let v: ~[&int] = xs.iter().skip(2).rev().collect();
+        assert_eq!(v, ~[&5, &4, &3, &2]);
+        let v: ~[&int] = xs.iter().take(4).rev().collect();
+        assert_eq!(v, ~[&3, &2, &1, &0]);
+        let v: ~[&int] = xs.iter().take(10).rev().collect();
+        assert_eq!(v, ~[&5, &4, &3, &2, &1, &0]);
+
+        let mut it = xs.iter().skip(1).take(3);
+        assert_eq!(it.next_back(), Some(&3));
+        assert_eq!(it.next(), Some(&1));
+        assert_eq!(it.next_back(), Some(&2));
+        assert_eq!(it.next_back(), None);
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn test_fused_iterator() {
+        fn exhausted<A, T: FusedIterator<A>>(mut it: T) -> bool {
+            for _ in it {}
+            it.next().is_none() && it.next().is_none()
+        }
+
+        // Yields `Some` again after every `None`.
+        struct Flicker { n: uint }
+        impl Iterator<uint> for Flicker {
+            fn next(&mut self) -> Option<uint> {
+                self.n += 1;
+                if self.n % 2 == 0 { None } else { Some(self.n) }
+            }
+        }
+
+        let xs = [1, 2, 3];
+        assert!(exhausted(xs.iter()));
+        assert!(exhausted(xs.iter().map(|&x| x * 2).enumerate()));
+        assert!(exhausted(range(0, 3).zip(xs.iter())));
+        assert!(exhausted(Flicker { n: 0 }.fuse()));
+
+        let mut flicker = Flicker { n: 0 };
+        assert_eq!(flicker.next(), Some(1));
+        assert_eq!(flicker.next(), None);
+        assert_eq!(flicker.next(), Some(3));
+    }
+
     #[test]
     fn test_double_ended_flat_map() {
         let u = [0u,1];
@@ -2875,6 +3729,8 @@ mod tests {
         // this test is only meaningful when sizeof uint < sizeof u64
         assert_eq!(range(uint::max_value - 1, uint::max_value).size_hint(), (1, Some(1)));
         assert_eq!(range(-10i, -1).size_hint(), (9, Some(9)));
+        assert_eq!(range(200, -5).size_hint(), (0, Some(0)));
+        assert_eq!(range(5u, 3).size_hint(), (0, Some(0)));
         assert_eq!(range(Foo, Foo).size_hint(), (0, None));
     }
 
@@ -2914,4 +3770,20 @@ mod tests {
         ys.mut_iter().reverse_();
         assert_eq!(ys, [5, 4, 3, 2, 1]);
     }
+
+    #[test]
+    fn test_partition_in_place() {
+        let mut ys = [1, 2, 3, 4, 5, 6, 7];
+        let n = ys.mut_iter().partition_in_place(|&x| x % 3 == 0);
+        assert_eq!(n, 2);
+        assert!(ys.slice_to(n).iter().all(|&x| x % 3 == 0));
+        assert!(ys.slice_from(n).iter().all(|&x| x % 3 != 0));
+
+        let mut all = [2, 4];
+        assert_eq!(all.mut_iter().partition_in_place(|&x| x % 2 == 0), 2);
+        assert_eq!(all, [2, 4]);
+
+        let mut none: [int, ..0] = [];
+        assert_eq!(none.mut_iter().partition_in_place(|_| true), 0);
+    }
 }
@@ -154,6 +154,8 @@ pub mod clone;
 pub mod hash;
 pub mod container;
 pub mod default;
+pub mod convert;
+pub mod error;
 pub mod any;
 
 
@@ -194,6 +196,7 @@ pub mod logging;
 pub mod util;
 pub mod routine;
 pub mod mem;
+pub mod alloc;
 
 
 /* Unsupported interfaces */
@@ -10,7 +10,12 @@
 
 //! Functions relating to memory layout
 
+use convert::{AsRef, AsMut};
+use default::Default;
+use ops::Drop;
+use ptr;
 use unstable::intrinsics;
+use util;
 
 /// Returns the size of a type
 #[inline]
@@ -68,10 +73,158 @@ pub fn pref_align_of_val<T>(_val: &T) -> uint {
     pref_align_of::<T>()
 }
 
+/// Replaces the value at a mutable location with its type's default value,
+/// returning the old value.
+#[inline]
+pub fn take<T: Default>(dest: &mut T) -> T {
+    util::replace(dest, Default::default())
+}
+
+/// A slot for a `T` that may not have been initialized yet.
+///
+/// The contained value is never dropped: call `assume_init` to take it out
+/// once it has been written. This is meant for out-parameters filled in by
+/// foreign code, which would otherwise need `intrinsics::uninit`.
+pub struct MaybeUninit<T> {
+    priv inner: MaybeUninitInner<T>,
+}
+
+// There are no unions, so the value is kept in a struct with a destructor
+// whose drop flag is left cleared. Drop glue checks the flag before running
+// the destructor or dropping any field, so the value is never touched.
+struct MaybeUninitInner<T> {
+    value: T,
+}
+
+#[unsafe_destructor]
+impl<T> Drop for MaybeUninitInner<T> {
+    fn drop(&mut self) {}
+}
+
+impl<T> MaybeUninit<T> {
+    /// A new slot with no value in it.
+    ///
+    /// The storage is zeroed, since that is what keeps the drop flag clear.
+    #[inline]
+    pub fn uninit() -> MaybeUninit<T> {
+        unsafe { intrinsics::init() }
+    }
+
+    /// A new slot holding `val`.
+    #[inline]
+    pub fn new(val: T) -> MaybeUninit<T> {
+        let mut slot = MaybeUninit::uninit();
+        slot.write(val);
+        slot
+    }
+
+    /// Moves `val` into the slot, returning a reference to it. Any value
+    /// already in the slot is overwritten without being dropped.
+    #[inline]
+    pub fn write<'a>(&'a mut self, val: T) -> &'a mut T {
+        unsafe {
+            intrinsics::move_val_init(&mut self.inner.value, val);
+        }
+        &mut self.inner.value
+    }
+
+    /// A pointer to the value, which may be uninitialized.
+    #[inline]
+    pub fn as_ptr(&self) -> *T {
+        ptr::to_unsafe_ptr(&self.inner.value)
+    }
+
+    /// A mutable pointer to the value, e.g. to pass to a foreign function
+    /// that fills it in.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        ptr::to_mut_unsafe_ptr(&mut self.inner.value)
+    }
+
+    /// Takes the value out of the slot.
+    ///
+    /// This is unsafe because the slot must have been initialized, either
+    /// by `new`, by `write` or through `as_mut_ptr`.
+    #[inline]
+    pub unsafe fn assume_init(self) -> T {
+        ptr::read_ptr(self.as_ptr())
+    }
+}
+
+/// A wrapper that keeps a `T` from being dropped.
+///
+/// Dropping a `ManuallyDrop<T>` leaves the value alone, e.g. because its
+/// ownership has been handed to foreign code. Deref is not overloadable, so
+/// the value is reached through `get` and `get_mut`, or `AsRef`/`AsMut`.
+pub struct ManuallyDrop<T> {
+    priv slot: MaybeUninit<T>,
+}
+
+impl<T> ManuallyDrop<T> {
+    /// Wraps `val` so that it is not dropped.
+    #[inline]
+    pub fn new(val: T) -> ManuallyDrop<T> {
+        ManuallyDrop { slot: MaybeUninit::new(val) }
+    }
+
+    /// Borrows the wrapped value.
+    #[inline]
+    pub fn get<'a>(&'a self) -> &'a T {
+        unsafe { &*self.slot.as_ptr() }
+    }
+
+    /// Mutably borrows the wrapped value.
+    #[inline]
+    pub fn get_mut<'a>(&'a mut self) -> &'a mut T {
+        unsafe { &mut *self.slot.as_mut_ptr() }
+    }
+
+    /// Takes the value out, so that it is dropped as usual again.
+    ///
+    /// This is unsafe because the value must not have been dropped already
+    /// with `drop`.
+    #[inline]
+    pub unsafe fn into_inner(self) -> T {
+        self.slot.assume_init()
+    }
+
+    /// Runs the wrapped value's destructor in place.
+    ///
+    /// This is unsafe because the value must not be used, or dropped again,
+    /// afterwards.
+    #[inline]
+    pub unsafe fn drop(&mut self) {
+        ptr::read_ptr(self.slot.as_ptr());
+    }
+}
+
+impl<T> AsRef<T> for ManuallyDrop<T> {
+    #[inline]
+    fn as_ref<'a>(&'a self) -> &'a T { self.get() }
+}
+
+impl<T> AsMut<T> for ManuallyDrop<T> {
+    #[inline]
+    fn as_mut<'a>(&'a mut self) -> &'a mut T { self.get_mut() }
+}
+
 #[cfg(test)]
 mod tests {
+    use prelude::*;
     use mem::*;
 
+    #[test]
+    fn test_take() {
+        let mut v = ~[1, 2, 3];
+        let w = take(&mut v);
+        assert!(v.is_empty());
+        assert_eq!(w, ~[1, 2, 3]);
+
+        let mut x = Some(~"hi");
+        assert_eq!(take(&mut x), Some(~"hi"));
+        assert!(x.is_none());
+    }
+
     #[test]
     fn size_of_basic() {
         assert_eq!(size_of::<u8>(), 1u);
@@ -149,4 +302,68 @@ mod tests {
         assert_eq!(pref_align_of_val(&1u16), 2u);
         assert_eq!(pref_align_of_val(&1u32), 4u);
     }
+
+    #[test]
+    fn maybe_uninit_write() {
+        use vec::OwnedVector;
+
+        let mut slot: MaybeUninit<~[int]> = MaybeUninit::uninit();
+        slot.write(~[1, 2]).push(3);
+        assert_eq!(unsafe { slot.assume_init() }, ~[1, 2, 3]);
+
+        let slot = MaybeUninit::new(~"hi");
+        assert!(unsafe { *slot.as_ptr() == ~"hi" });
+        assert_eq!(unsafe { slot.assume_init() }, ~"hi");
+    }
+
+    #[test]
+    fn maybe_uninit_never_drops() {
+        struct R {
+            i: @mut int,
+        }
+
+        #[unsafe_destructor]
+        impl ::ops::Drop for R {
+            fn drop(&mut self) { *(self.i) += 1; }
+        }
+
+        let i = @mut 0;
+        {
+            let _empty: MaybeUninit<R> = MaybeUninit::uninit();
+            let mut full = MaybeUninit::new(R { i: i });
+            full.write(R { i: i });
+        }
+        assert_eq!(*i, 0);
+
+        unsafe { MaybeUninit::new(R { i: i }).assume_init(); }
+        assert_eq!(*i, 1);
+    }
+
+    #[test]
+    fn manually_drop() {
+        struct R {
+            i: @mut int,
+        }
+
+        #[unsafe_destructor]
+        impl ::ops::Drop for R {
+            fn drop(&mut self) { *(self.i) += 1; }
+        }
+
+        let i = @mut 0;
+        {
+            let mut m = ManuallyDrop::new(R { i: i });
+            *m.get_mut().i += 10;
+        }
+        assert_eq!(*i, 10);
+
+        let mut m = ManuallyDrop::new(R { i: i });
+        unsafe { m.drop(); }
+        assert_eq!(*i, 11);
+
+        let m = ManuallyDrop::new(R { i: i });
+        assert_eq!(*m.get().i, 11);
+        unsafe { m.into_inner(); }
+        assert_eq!(*i, 12);
+    }
 }
@@ -242,6 +242,16 @@ impl<T> Option<T> {
         }
     }
 
+    /// Returns `Some((a, b))` if the option is `Some(a)` and `other` is
+    /// `Some(b)`, otherwise returns `None`.
+    #[inline]
+    pub fn zip<U>(self, other: Option<U>) -> Option<(T, U)> {
+        match (self, other) {
+            (Some(a), Some(b)) => Some((a, b)),
+            _ => None,
+        }
+    }
+
     /// Returns the option if it contains a value, otherwise returns `optb`.
     #[inline]
     pub fn or(self, optb: Option<T>) -> Option<T> {
@@ -271,15 +281,23 @@ impl<T> Option<T> {
         util::replace(self, None)
     }
 
-    /// Filters an optional value using a given function.
-    #[inline(always)]
-    pub fn filtered(self, f: |t: &T| -> bool) -> Option<T> {
+    /// Returns `None` if the option is `None` or `f` returns false for the
+    /// contained value, otherwise returns the option unchanged.
+    #[inline]
+    pub fn filter(self, f: |t: &T| -> bool) -> Option<T> {
         match self {
-            Some(x) => if(f(&x)) {Some(x)} else {None},
+            Some(x) => if f(&x) { Some(x) } else { None },
             None => None
         }
     }
 
+    /// Filters an optional value using a given function. The same as
+    /// `filter`.
+    #[inline(always)]
+    pub fn filtered(self, f: |t: &T| -> bool) -> Option<T> {
+        self.filter(f)
+    }
+
     /// Applies a function zero or more times until the result is `None`.
     #[inline]
     pub fn while_some(self, blk: |v: T| -> Option<T>) {
@@ -359,6 +377,18 @@ impl<T: Default> Option<T> {
     }
 }
 
+impl<T, U> Option<(T, U)> {
+    /// Splits an option of a pair into a pair of options: `Some((a, b))`
+    /// gives `(Some(a), Some(b))` and `None` gives `(None, None)`.
+    #[inline]
+    pub fn unzip(self) -> (Option<T>, Option<U>) {
+        match self {
+            Some((a, b)) => (Some(a), Some(b)),
+            None => (None, None),
+        }
+    }
+}
+
 /////////////////////////////////////////////////////////////////////////////
 // Constructor extension trait
 /////////////////////////////////////////////////////////////////////////////
@@ -669,6 +699,27 @@ mod tests {
         assert!(modified_stuff.is_none());
     }
 
+    #[test]
+    fn test_filter() {
+        assert_eq!(Some(4).filter(|&x| x % 2 == 0), Some(4));
+        assert_eq!(Some(3).filter(|&x| x % 2 == 0), None);
+        assert_eq!(None::<int>.filter(|_| true), None);
+
+        let mut seen = 0;
+        assert_eq!(Some(~"yes").filter(|s| { seen += 1; *s == ~"yes" }), Some(~"yes"));
+        assert_eq!(seen, 1);
+    }
+
+    #[test]
+    fn test_zip_unzip() {
+        assert_eq!(Some(1).zip(Some(~"a")), Some((1, ~"a")));
+        assert_eq!(Some(1).zip(None::<int>), None);
+        assert_eq!(None::<int>.zip(Some(2)), None);
+
+        assert_eq!(Some((1, ~"a")).unzip(), (Some(1), Some(~"a")));
+        assert_eq!(None::<(int, int)>.unzip(), (None, None));
+    }
+
     #[test]
     fn test_iter() {
         let val = 5;
@@ -32,6 +32,7 @@
 use c_str::CString;
 use clone::Clone;
 use container::Container;
+use error::Error;
 use iter::range;
 use libc;
 use libc::{c_char, c_void, c_int, size_t};
@@ -933,6 +934,26 @@ impl to_str::ToStr for MapError {
     }
 }
 
+impl Error for MapError {
+    fn description<'a>(&'a self) -> &'a str {
+        match *self {
+            ErrFdNotAvail => "fd not available for reading or writing",
+            ErrInvalidFd => "Invalid fd",
+            ErrUnaligned => "Unaligned address, invalid flags, \
+                             negative length or unaligned offset",
+            ErrNoMapSupport=> "File doesn't support mapping",
+            ErrNoMem => "Invalid address, or not enough available memory",
+            ErrUnknown(*) => "Unknown error",
+            ErrUnsupProt => "Protection mode unsupported",
+            ErrUnsupOffset => "Offset in virtual memory mode is unsupported",
+            ErrAlreadyExists => "File mapping for specified file already exists",
+            ErrVirtualAlloc(*) => "VirtualAlloc failure",
+            ErrCreateFileMappingW(*) => "CreateFileMappingW failure",
+            ErrMapViewOfFile(*) => "MapViewOfFile failure"
+        }
+    }
+}
+
 #[cfg(unix)]
 impl MemoryMap {
     /// Create a new mapping with the given `options`, at least `min_len` bytes long.
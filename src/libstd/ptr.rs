@@ -104,6 +104,29 @@ pub unsafe fn copy_nonoverlapping_memory<T,P:RawPtr<T>>(dst: *mut T,
     intrinsics::copy_nonoverlapping_memory(dst, cast::transmute_immut_unsafe(src), count)
 }
 
+/**
+ * Copies `count` elements (not bytes) from `src` to `dst`, like `memmove`.
+ * The source and destination may overlap.
+ *
+ * This is `copy_memory` with the arguments in source, destination order.
+ */
+#[inline]
+pub unsafe fn copy<T>(src: *T, dst: *mut T, count: uint) {
+    intrinsics::copy_memory(dst, src, count)
+}
+
+/**
+ * Copies `count` elements (not bytes) from `src` to `dst`, like `memcpy`.
+ * The source and destination may *not* overlap.
+ *
+ * This is `copy_nonoverlapping_memory` with the arguments in source,
+ * destination order.
+ */
+#[inline]
+pub unsafe fn copy_nonoverlapping<T>(src: *T, dst: *mut T, count: uint) {
+    intrinsics::copy_nonoverlapping_memory(dst, src, count)
+}
+
 /**
  * Invokes memset on the specified pointer, setting `count * size_of::<T>()`
  * bytes of memory starting at `dst` to `c`.
@@ -161,6 +184,27 @@ pub unsafe fn read_ptr<T>(src: *T) -> T {
     tmp
 }
 
+/**
+ * Reads the value from `*src` with a volatile load, which the optimizer may
+ * neither remove nor reorder with other volatile accesses. Does not copy
+ * `*src`. This is meant for memory-mapped I/O.
+ */
+#[inline]
+#[cfg(not(stage0))]
+pub unsafe fn read_volatile<T>(src: *T) -> T {
+    intrinsics::volatile_load(src)
+}
+
+/**
+ * Writes `val` to `*dst` with a volatile store, without dropping the old
+ * value. This is meant for memory-mapped I/O.
+ */
+#[inline]
+#[cfg(not(stage0))]
+pub unsafe fn write_volatile<T>(dst: *mut T, val: T) {
+    intrinsics::volatile_store(dst, val)
+}
+
 /**
  * Reads the value from `*src` and nulls it out.
  * This currently prevents destructors from executing.
@@ -488,6 +532,25 @@ pub mod ptr_tests {
         }
     }
 
+    #[test]
+    fn test_copy() {
+        let mut xs = ~[1, 2, 3, 4, 5];
+        unsafe {
+            // Overlapping: shift everything one place to the right.
+            let p = vec::raw::to_mut_ptr(xs);
+            copy(p as *int, mut_offset(p, 1), 4);
+        }
+        assert_eq!(xs, ~[1, 1, 2, 3, 4]);
+
+        let src = [7, 8, 9];
+        let mut dst = [0, 0, 0, 0];
+        unsafe {
+            copy_nonoverlapping(vec::raw::to_ptr(src),
+                                mut_offset(vec::raw::to_mut_ptr(dst), 1), 3);
+        }
+        assert_eq!(dst, [0, 7, 8, 9]);
+    }
+
     #[test]
     fn test_position() {
         use libc::c_char;
@@ -704,4 +767,24 @@ pub mod ptr_tests {
         unsafe { set_memory(ptr, 5u8, xs.len()); }
         assert_eq!(xs, [5u8, ..20]);
     }
+
+    #[test]
+    fn test_volatile() {
+        let mut x = 7u32;
+        unsafe {
+            assert_eq!(read_volatile(&x as *u32), 7);
+            write_volatile(&mut x as *mut u32, 9);
+        }
+        assert_eq!(x, 9);
+
+        let mut s = (1u64, ~"a");
+        unsafe {
+            let (n, old) = read_volatile(&s as *(u64, ~str));
+            assert_eq!(n, 1);
+            // The old string is not dropped by the write; `old` owns it now.
+            write_volatile(&mut s as *mut (u64, ~str), (2u64, ~"b"));
+            assert_eq!(old, ~"a");
+        }
+        assert_eq!(s, (2u64, ~"b"));
+    }
 }
@@ -29,6 +29,7 @@ use vec;
 /// It is further recommended for `E` to be a descriptive error type, eg a `enum` for
 /// all possible errors cases.
 #[deriving(Clone, DeepClone, Eq, Ord, TotalEq, TotalOrd, ToStr)]
+#[must_use]
 pub enum Result<T, E> {
     /// Contains the successful result value
     Ok(T),
@@ -163,6 +164,43 @@ impl<T, E: ToStr> Result<T, E> {
         }
     }
 
+    /// Applies `f` to a contained `Ok` value, or `default` to a contained
+    /// `Err` value, and returns the outcome.
+    ///
+    /// The fallback is only computed when it is needed, so it may be
+    /// expensive to produce.
+    #[inline]
+    pub fn map_or_else<U>(self, default: |E| -> U, f: |T| -> U) -> U {
+        match self {
+          Ok(t) => f(t),
+          Err(e) => default(e)
+        }
+    }
+
+    /// Calls `f` with a reference to the contained `Ok` value, then returns
+    /// the result unchanged. Does nothing for an `Err` value.
+    ///
+    /// This function is useful for logging or debugging in a chain of calls.
+    #[inline]
+    pub fn inspect(self, f: |&T|) -> Result<T, E> {
+        match self {
+            Ok(ref t) => f(t),
+            Err(_) => {}
+        }
+        self
+    }
+
+    /// Calls `f` with a reference to the contained `Err` value, then returns
+    /// the result unchanged. Does nothing for an `Ok` value.
+    #[inline]
+    pub fn inspect_err(self, f: |&E|) -> Result<T, E> {
+        match self {
+            Ok(_) => {}
+            Err(ref e) => f(e),
+        }
+        self
+    }
+
     /////////////////////////////////////////////////////////////////////////
     // Iterator constructors
     /////////////////////////////////////////////////////////////////////////
@@ -254,6 +292,18 @@ impl<T, E: ToStr> Result<T, E> {
     }
 }
 
+impl<T, E> Result<Result<T, E>, E> {
+    /// Removes one level of nesting: `Ok(Ok(x))` gives `Ok(x)`, while
+    /// `Ok(Err(e))` and `Err(e)` both give `Err(e)`.
+    #[inline]
+    pub fn flatten(self) -> Result<T, E> {
+        match self {
+            Ok(inner) => inner,
+            Err(e) => Err(e),
+        }
+    }
+}
+
 /////////////////////////////////////////////////////////////////////////////
 // Constructor extension trait
 /////////////////////////////////////////////////////////////////////////////
@@ -458,6 +508,34 @@ mod tests {
         assert_eq!(op2().or_else(|e| Err::<int, ~str>(e + "!")).unwrap_err(), ~"sadface!");
     }
 
+    #[test]
+    pub fn test_map_or_else() {
+        let mut called = false;
+        assert_eq!(op1().map_or_else(|_| { called = true; 0 }, |x| x + 1), 667);
+        assert!(!called);
+        assert_eq!(op2().map_or_else(|e| if e == ~"sadface" { 1 } else { 2 }, |x| x), 1);
+    }
+
+    #[test]
+    pub fn test_inspect() {
+        let mut seen = 0;
+        let r = op1().inspect(|x| seen = *x);
+        assert_eq!(r.inspect_err(|_| seen = -1), Ok(666));
+        assert_eq!(seen, 666);
+
+        let mut seen = ~"";
+        let r = op2().inspect(|_| seen = ~"ok");
+        assert_eq!(r.inspect_err(|e| seen = e.clone()), Err(~"sadface"));
+        assert_eq!(seen, ~"sadface");
+    }
+
+    #[test]
+    pub fn test_flatten() {
+        assert_eq!(Ok::<Result<int, ~str>, ~str>(Ok(1)).flatten(), Ok(1));
+        assert_eq!(Ok::<Result<int, ~str>, ~str>(Err(~"inner")).flatten(), Err(~"inner"));
+        assert_eq!(Err::<Result<int, ~str>, ~str>(~"outer").flatten(), Err(~"outer"));
+    }
+
     #[test]
     pub fn test_impl_iter() {
         let mut valid = false;
@@ -513,6 +513,25 @@ impl Unwinder {
         }
     }
 
+    /// Runs `f`, stopping any failure inside it from unwinding further.
+    /// Returns the cause of the failure, or `None` if `f` returned normally.
+    ///
+    /// This may be called while already unwinding, e.g. from a destructor
+    /// run by a failure. That unwind is set aside while `f` runs, and
+    /// carries on unchanged afterwards.
+    pub fn catch(&mut self, f: ||) -> Option<~Any> {
+        let outer_unwinding = self.unwinding;
+        let outer_cause = self.cause.take();
+        self.unwinding = false;
+
+        self.try(f);
+        let cause = if self.unwinding { self.cause.take() } else { None };
+
+        self.unwinding = outer_unwinding;
+        self.cause = outer_cause;
+        cause
+    }
+
     pub fn begin_unwind(&mut self, cause: ~Any) -> ! {
         self.unwinding = true;
         self.cause = Some(cause);
@@ -631,6 +650,7 @@ pub fn begin_unwind<M: Any + Send>(msg: M, file: &'static str, line: uint) -> !
     use rt::local::Local;
     use rt::task::Task;
     use str::Str;
+    use task;
     use unstable::intrinsics;
 
     unsafe {
@@ -670,7 +690,17 @@ pub fn begin_unwind<M: Any + Send>(msg: M, file: &'static str, line: uint) -> !
             //      due to mismanagment of its own kill flag, so calling our own
             //      logger in its current state is a bit of a problem.
 
-            rterrln!("task '{}' failed at '{}', {}:{}", n, msg_s, file, line);
+            match task::current_fail_hook() {
+                Some(hook) => hook(&task::FailInfo {
+                    task_name: n,
+                    msg: msg_s,
+                    file: file,
+                    line: line,
+                }),
+                None => {
+                    rterrln!("task '{}' failed at '{}', {}:{}", n, msg_s, file, line);
+                }
+            }
 
             if (*task).unwinder.unwinding {
                 rtabort!("unwinding again");
@@ -685,6 +715,27 @@ pub fn begin_unwind<M: Any + Send>(msg: M, file: &'static str, line: uint) -> !
 mod test {
     use super::*;
     use rt::test::*;
+    use prelude::*;
+
+    #[test]
+    fn unwinder_catch() {
+        let mut u = Unwinder { unwinding: false, cause: None };
+        let p: *mut Unwinder = &mut u;
+
+        assert!(u.catch(|| {}).is_none());
+        let cause = u.catch(|| unsafe { (*p).begin_unwind(~1 as ~Any) });
+        assert_eq!(*cause.unwrap().move::<int>().unwrap(), 1);
+        assert!(!u.unwinding);
+
+        // An unwind already in progress is left alone.
+        u.unwinding = true;
+        u.cause = Some(~2 as ~Any);
+        assert!(u.catch(|| {}).is_none());
+        let cause = u.catch(|| unsafe { (*p).begin_unwind(~3 as ~Any) });
+        assert_eq!(*cause.unwrap().move::<int>().unwrap(), 3);
+        assert!(u.unwinding);
+        assert_eq!(*u.cause.take_unwrap().move::<int>().unwrap(), 2);
+    }
 
     #[test]
     fn local_heap() {
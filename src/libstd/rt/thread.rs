@@ -83,21 +83,21 @@ fn native_thread_join(native: rust_thread) {
 #[cfg(unix)]
 fn native_thread_create(thread_start: extern "C" fn(*libc::c_void) -> rust_thread_return,
                         tramp: ~proc()) -> rust_thread {
-    use unstable::intrinsics;
-    let mut native: libc::pthread_t = unsafe { intrinsics::uninit() };
+    use mem::MaybeUninit;
+    let mut native: MaybeUninit<libc::pthread_t> = MaybeUninit::uninit();
 
     unsafe {
         use libc::consts::os::posix01::PTHREAD_CREATE_JOINABLE;
 
-        let mut attr: libc::pthread_attr_t = intrinsics::uninit();
-        assert!(pthread_attr_init(&mut attr) == 0);
-        assert!(pthread_attr_setstacksize(&mut attr, DEFAULT_STACK_SIZE) == 0);
-        assert!(pthread_attr_setdetachstate(&mut attr, PTHREAD_CREATE_JOINABLE) == 0);
+        let mut attr: MaybeUninit<libc::pthread_attr_t> = MaybeUninit::uninit();
+        assert!(pthread_attr_init(attr.as_mut_ptr()) == 0);
+        assert!(pthread_attr_setstacksize(attr.as_mut_ptr(), DEFAULT_STACK_SIZE) == 0);
+        assert!(pthread_attr_setdetachstate(attr.as_mut_ptr(), PTHREAD_CREATE_JOINABLE) == 0);
 
         let ptr: *libc::c_void = cast::transmute(tramp);
-        assert!(pthread_create(&mut native, &attr, thread_start, ptr) == 0);
+        assert!(pthread_create(native.as_mut_ptr(), attr.as_ptr(), thread_start, ptr) == 0);
+        native.assume_init()
     }
-    native
 }
 
 #[cfg(unix)]
@@ -62,6 +62,7 @@ use rt::in_green_task_context;
 use rt::local::Local;
 use rt::task::{UnwindResult, Success, Failure};
 use send_str::{SendStr, IntoSendStr};
+use unstable::mutex::{Mutex, MUTEX_INIT};
 use util;
 
 #[cfg(test)] use any::Any;
@@ -424,6 +425,36 @@ pub fn try<T:Send>(f: proc() -> T) -> Result<T, ~Any> {
     task.try(f)
 }
 
+pub fn catch_unwind<T>(f: || -> T) -> Result<T, ~Any> {
+    /*!
+     * Execute a function in the current task and return either its return
+     * value or the cause of its failure.
+     *
+     * Unlike `try`, this does not spawn a task, so `f` may borrow from the
+     * caller. On failure, whatever `f` was in the middle of changing is left
+     * as it was when the failure occurred; the destructors of values owned
+     * by `f` have run. Only Rust failures are caught, foreign exceptions
+     * continue to propagate.
+     *
+     * # Failure
+     *
+     * Fails if called outside of a task.
+     */
+
+    use rt::task::Task;
+
+    assert!(in_green_task_context(), "catch_unwind outside of a task");
+    let mut result = None;
+    let cause = unsafe {
+        let task: *mut Task = Local::unsafe_borrow();
+        (*task).unwinder.catch(|| result = Some(f()))
+    };
+    match cause {
+        Some(cause) => Err(cause),
+        None => Ok(result.unwrap())
+    }
+}
+
 
 /* Lifecycle functions */
 
@@ -462,6 +493,66 @@ pub fn failing() -> bool {
     Local::borrow(|local: &mut Task| local.unwinder.unwinding)
 }
 
+
+/* Failure reporting */
+
+/// Where and why a task failed, as given to a failure hook.
+pub struct FailInfo<'self> {
+    /// The name of the failing task
+    task_name: &'self str,
+    /// The failure message
+    msg: &'self str,
+    /// The source file containing the failure
+    file: &'static str,
+    /// The line of the failure
+    line: uint,
+}
+
+/// A function reporting task failures, in place of the default message.
+pub type FailHook = fn(&FailInfo);
+
+static mut fail_hook_lock: Mutex = MUTEX_INIT;
+static mut fail_hook: Option<FailHook> = None;
+
+pub fn set_fail_hook(hook: FailHook) {
+    /*!
+     * Report task failures by calling `hook` instead of printing the
+     * default "task '<name>' failed at ..." message.
+     *
+     * The hook applies to every task, and is called by the failing task
+     * before it starts unwinding. A failure inside the hook aborts the
+     * process.
+     */
+
+    unsafe {
+        fail_hook_lock.lock();
+        fail_hook = Some(hook);
+        fail_hook_lock.unlock();
+    }
+}
+
+pub fn take_fail_hook() -> Option<FailHook> {
+    //! Remove the current failure hook, restoring the default message.
+
+    unsafe {
+        fail_hook_lock.lock();
+        let hook = fail_hook;
+        fail_hook = None;
+        fail_hook_lock.unlock();
+        hook
+    }
+}
+
+#[doc(hidden)]
+pub fn current_fail_hook() -> Option<FailHook> {
+    unsafe {
+        fail_hook_lock.lock();
+        let hook = fail_hook;
+        fail_hook_lock.unlock();
+        hook
+    }
+}
+
 // The following 8 tests test the following 2^3 combinations:
 // {un,}linked {un,}supervised failure propagation {up,down}wards.
 
@@ -604,6 +695,69 @@ fn test_try_fail() {
     }
 }
 
+#[test]
+fn test_catch_unwind() {
+    let mut x = 1;
+    assert_eq!(catch_unwind(|| { x += 1; x }).unwrap(), 2);
+
+    match catch_unwind(|| { x += 1; if x > 0 { fail!("caught") } x }) {
+        Err(e) => assert_eq!(*e.move::<&'static str>().unwrap(), "caught"),
+        Ok(_) => fail!()
+    }
+    assert_eq!(x, 3);
+
+    // The task is still healthy, and can fail and catch again.
+    assert!(catch_unwind(|| { fail!(); }).is_err());
+    assert_eq!(catch_unwind(|| 5).unwrap(), 5);
+}
+
+#[test]
+fn test_catch_unwind_while_unwinding() {
+    // Catches from its destructor, while the task fails.
+    struct Catcher { chan: SharedChan<bool> }
+    impl Drop for Catcher {
+        fn drop(&mut self) {
+            self.chan.send(catch_unwind(|| 5).is_ok());
+            self.chan.send(catch_unwind(|| { fail!("inner"); }).is_err());
+        }
+    }
+
+    let (po, ch) = stream();
+    let ch = SharedChan::new(ch);
+    let result = do try {
+        let _c = Catcher { chan: ch.clone() };
+        fail!("outer");
+    };
+    assert!(po.recv());
+    assert!(po.recv());
+
+    // The failure carried on, with its own cause.
+    match result {
+        Err(e) => assert_eq!(*e.move::<&'static str>().unwrap(), "outer"),
+        Ok(()) => fail!()
+    }
+}
+
+#[test]
+fn test_fail_hook() {
+    use unstable::atomics::{AtomicUint, INIT_ATOMIC_UINT, SeqCst};
+
+    static mut hits: AtomicUint = INIT_ATOMIC_UINT;
+
+    // Failures from other tests may reach the hook while it is installed.
+    fn hook(info: &FailInfo) {
+        if info.msg == "hooked" && info.file == file!() {
+            unsafe { hits.fetch_add(1, SeqCst); }
+        }
+    }
+
+    set_fail_hook(hook);
+    assert!(do try { fail!("hooked"); }.is_err());
+    assert!(take_fail_hook().is_some());
+    assert!(take_fail_hook().is_none());
+    unsafe { assert_eq!(hits.load(SeqCst), 1); }
+}
+
 #[cfg(test)]
 fn get_sched_id() -> int {
     Local::borrow(|sched: &mut ::rt::sched::Scheduler| {
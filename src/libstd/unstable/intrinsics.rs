@@ -326,6 +326,16 @@ extern "rust-intrinsic" {
     /// Create an uninitialized value.
     pub fn uninit<T>() -> T;
 
+    /// Load a value from `src` with a volatile load, which the optimizer
+    /// may neither remove nor reorder with other volatile operations.
+    #[cfg(not(stage0))]
+    pub fn volatile_load<T>(src: *T) -> T;
+
+    /// Store `val` to `dst` with a volatile store. Drop glue is not run on
+    /// the destination.
+    #[cfg(not(stage0))]
+    pub fn volatile_store<T>(dst: *mut T, val: T);
+
     /// Move a value out of scope without running drop glue.
     ///
     /// `forget` is unsafe because the caller is responsible for
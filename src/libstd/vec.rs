@@ -2436,6 +2436,8 @@ pub type RevIterator<'self, T> = Invert<VecIterator<'self, T>>;
 
 impl<'self, T> ExactSize<&'self T> for VecIterator<'self, T> {}
 impl<'self, T> ExactSize<&'self mut T> for VecMutIterator<'self, T> {}
+impl<'self, T> FusedIterator<&'self T> for VecIterator<'self, T> {}
+impl<'self, T> FusedIterator<&'self mut T> for VecMutIterator<'self, T> {}
 
 impl<'self, T> Clone for VecIterator<'self, T> {
     fn clone(&self) -> VecIterator<'self, T> { *self }
@@ -2505,6 +2507,9 @@ impl<T> Iterator<T> for MoveRevIterator<T> {
     }
 }
 
+impl<T> FusedIterator<T> for MoveIterator<T> {}
+impl<T> FusedIterator<T> for MoveRevIterator<T> {}
+
 impl<A> FromIterator<A> for ~[A] {
     fn from_iterator<T: Iterator<A>>(iterator: &mut T) -> ~[A] {
         let (lower, _) = iterator.size_hint();
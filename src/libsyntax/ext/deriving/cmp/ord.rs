@@ -60,13 +60,20 @@ fn cs_op(less: bool, equal: bool, cx: @ExtCtxt, span: Span, substr: &Substructur
             `ast::lt`
 
             ```
-            self.f1 < other.f1 || (!(other.f1 < self.f1) &&
-                (self.f2 < other.f2 || (!(other.f2 < self.f2) &&
+            self.f1 < other.f1 || (self.f1 <= other.f1 && other.f1 <= self.f1 &&
+                (self.f2 < other.f2 || (self.f2 <= other.f2 && other.f2 <= self.f2 &&
                     (false)
                 ))
             )
             ```
 
+            A field only falls through to the next one when it is equal,
+            which is tested with `<=` both ways rather than as "neither is
+            less": `Ord` is a partial order, and two incomparable fields
+            (such as a NaN and a number) must make the whole comparison
+            false instead of being skipped. For a total order the two
+            tests agree.
+
             The optimiser should remove the redundancy. We explicitly
             get use the binops to avoid auto-deref derefencing too many
             layers of pointers, if the type includes pointers.
@@ -80,12 +87,15 @@ fn cs_op(less: bool, equal: bool, cx: @ExtCtxt, span: Span, substr: &Substructur
                                      cx.expr_deref(span, self_f),
                                      cx.expr_deref(span, other_f));
 
-            let not_cmp = cx.expr_unary(span, ast::UnNot,
-                                        cx.expr_binary(span, op,
-                                                       cx.expr_deref(span, other_f),
-                                                       cx.expr_deref(span, self_f)));
+            let self_le = cx.expr_binary(span, ast::BiLe,
+                                         cx.expr_deref(span, self_f),
+                                         cx.expr_deref(span, other_f));
+            let other_le = cx.expr_binary(span, ast::BiLe,
+                                          cx.expr_deref(span, other_f),
+                                          cx.expr_deref(span, self_f));
+            let fields_eq = cx.expr_binary(span, ast::BiAnd, self_le, other_le);
 
-            let and = cx.expr_binary(span, ast::BiAnd, not_cmp, subexpr);
+            let and = cx.expr_binary(span, ast::BiAnd, fields_eq, subexpr);
             cx.expr_binary(span, ast::BiOr, cmp, and)
         },
         cx.expr_bool(span, equal),
@@ -935,6 +935,12 @@ pub fn std_macros() -> @str {
     macro_rules! println (
         ($($arg:tt)*) => (format_args!(::std::io::stdio::println_args, $($arg)*))
     )
+    macro_rules! eprint (
+        ($($arg:tt)*) => (format_args!(::std::io::stdio::eprint_args, $($arg)*))
+    )
+    macro_rules! eprintln (
+        ($($arg:tt)*) => (format_args!(::std::io::stdio::eprintln_args, $($arg)*))
+    )
 
     macro_rules! local_data_key (
         ($name:ident: $ty:ty) => (
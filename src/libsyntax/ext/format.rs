@@ -521,6 +521,9 @@ impl Context {
                     parse::AlignRight => {
                         self.ecx.path_global(sp, parsepath("AlignRight"))
                     }
+                    parse::AlignCenter => {
+                        self.ecx.path_global(sp, parsepath("AlignCenter"))
+                    }
                     parse::AlignUnknown => {
                         self.ecx.path_global(sp, parsepath("AlignUnknown"))
                     }
@@ -671,6 +674,8 @@ impl Context {
                     "b" => "Bool",
                     "c" => "Char",
                     "d" | "i" => "Signed",
+                    "e" => "LowerExp",
+                    "E" => "UpperExp",
                     "f" => "Float",
                     "o" => "Octal",
                     "p" => "Pointer",
@@ -0,0 +1,34 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Overlapping impls are reported once per pair, at the later impl.
+
+trait Foo {}
+
+struct Bar<T>;
+
+impl Foo for Bar<int> {}
+
+impl<T> Foo for Bar<T> {} //~ ERROR conflicting implementations for trait `Foo`
+
+impl Foo for ~int {}
+
+impl Foo for ~int {} //~ ERROR conflicting implementations for trait `Foo`
+
+// These apply to distinct types, so they do not conflict.
+trait Baz {}
+
+impl Baz for Bar<int> {}
+
+impl Baz for Bar<uint> {}
+
+impl<T> Baz for ~T {}
+
+fn main() {}
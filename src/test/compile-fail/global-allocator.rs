@@ -0,0 +1,22 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::alloc::System;
+
+#[global_allocator]
+static A: System = System;
+
+#[global_allocator]
+static B: System = System; //~ ERROR multiple `#[global_allocator]` statics
+
+#[global_allocator]
+fn f() {} //~ ERROR `#[global_allocator]` can only be applied to a static
+
+fn main() {}
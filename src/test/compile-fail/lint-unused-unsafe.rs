@@ -40,6 +40,16 @@ unsafe fn bad7() {
         }
     }
 }
+fn bad8() {
+    let x = 1;
+    unsafe { &x as *int; }               //~ ERROR: unnecessary `unsafe` block
+}
+fn bad9() {
+    unsafe {                             //~ ERROR: unnecessary `unsafe` block
+        #[cfg(not_a_real_cfg)]
+        fn f() { unsafe { unsf() } }
+    }
+}
 
 unsafe fn good0() { unsf() }
 fn good1() { unsafe { unsf() } }
@@ -59,6 +69,11 @@ fn good2() {
 unsafe fn good3() { foo::bar() }
 fn good4() { unsafe { foo::bar() } }
 
+static mut COUNT: int = 0;
+fn good5() { unsafe { COUNT += 1; } }
+fn good6(p: *int) -> int { unsafe { *p } }
+#[cfg(not_a_real_cfg)] fn good7() { unsafe {} }
+
 #[allow(unused_unsafe)] fn allowed() { unsafe {} }
 
 fn main() {}
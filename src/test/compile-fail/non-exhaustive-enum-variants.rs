@@ -0,0 +1,47 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Matches on enums must cover every variant, and report one that is missing.
+
+enum Color { Red, Green, Blue }
+
+enum Shape {
+    Circle(f64),
+    Rect(f64, f64),
+    Named { name: ~str },
+}
+
+fn main() {
+    match Red { //~ ERROR non-exhaustive patterns: Blue not covered
+        Red => {}
+        Green => {}
+    }
+    match Circle(1.0) { //~ ERROR non-exhaustive patterns: Named not covered
+        Circle(_) => {}
+        Rect(*) => {}
+    }
+    match (Red, Green) { //~ ERROR non-exhaustive patterns
+        (Red, _) => {}
+        (Green, _) => {}
+    }
+    match Some(Green) { //~ ERROR non-exhaustive patterns
+        Some(Red) | Some(Green) => {}
+        None => {}
+    }
+    // Exhaustive, so no error.
+    match Blue {
+        Red | Green => {}
+        Blue => {}
+    }
+    match Named { name: ~"x" } {
+        Circle(_) | Rect(_, _) => {}
+        Named { _ } => {}
+    }
+}
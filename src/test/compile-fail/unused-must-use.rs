@@ -0,0 +1,54 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[deny(unused_must_use)];
+
+#[must_use]
+struct Token;
+
+#[must_use]
+fn important() -> int { 1 }
+
+fn result() -> Result<int, ()> { Ok(1) }
+fn token() -> Token { Token }
+fn plain() -> int { 1 }
+
+struct Checker;
+
+impl Checker {
+    #[must_use]
+    fn check(&self) -> bool { true }
+    fn run(&self) -> bool { true }
+    #[must_use]
+    fn new_checked() -> bool { true }
+}
+
+trait Validate {
+    #[must_use]
+    fn validate(&self) -> bool;
+}
+
+fn validate_all<T: Validate>(t: &T) {
+    t.validate(); //~ ERROR: unused return value of
+}
+
+fn main() {
+    result(); //~ ERROR: unused `Result` that must be used
+    token(); //~ ERROR: unused `Token` that must be used
+    important(); //~ ERROR: unused return value of `important` that must be used
+    Checker.check(); //~ ERROR: unused return value of
+    Checker.run();
+    Checker::new_checked(); //~ ERROR: unused return value of
+
+    plain();
+    let _ = result();
+    let _x = token();
+    let _y = important();
+}
@@ -0,0 +1,21 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// error-pattern:incomplete UTF-8 sequence
+
+use std::fmt;
+use std::io::Writer;
+
+fn main() {
+    let mut s = ~"";
+    let mut a = fmt::Adapter::new(&mut s);
+    // The first byte of a snowman, never completed.
+    a.write("☃".as_bytes().slice_to(1));
+}
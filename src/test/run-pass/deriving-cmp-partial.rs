@@ -0,0 +1,44 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that derived `Ord` compares fields lexicographically, and that an
+// incomparable field makes the whole comparison false instead of being
+// skipped over.
+
+#[deriving(Eq, Ord)]
+struct P {
+    x: f64,
+    y: int
+}
+
+#[deriving(Eq, Ord)]
+enum E {
+    A(f64),
+    B(int)
+}
+
+pub fn main() {
+    let nan = 0.0f64 / 0.0;
+
+    assert!(P { x: 1.0, y: 1 } < P { x: 1.0, y: 2 });
+    assert!(P { x: 1.0, y: 5 } < P { x: 2.0, y: 0 });
+    assert!(P { x: 1.0, y: 2 } <= P { x: 1.0, y: 2 });
+    assert!(P { x: 1.0, y: 2 } >= P { x: 1.0, y: 2 });
+
+    let a = P { x: nan, y: 1 };
+    let b = P { x: 1.0, y: 2 };
+    assert!(!(a < b) && !(a > b) && !(a <= b) && !(a >= b));
+    assert!(!(a <= a) && !(a >= a));
+
+    // Variants are ordered before their fields are looked at.
+    assert!(A(5.0) < B(0));
+    assert!(A(nan) < B(0));
+    assert!(!(A(nan) < A(1.0)) && !(A(nan) > A(1.0)));
+}
@@ -82,10 +82,15 @@ pub fn main() {
     t!(format!("{:d}", B), "adios");
     t!(format!("foo {:s} ☃☃☃☃☃☃", "bar"), "foo bar ☃☃☃☃☃☃");
     t!(format!("{1} {0}", 0, 1), "1 0");
+    t!(format!("{0} {1} {0}", "a", "b"), "a b a");
+    t!(format!("{1:3d}|{0:s}|{1:d}", "x", 7), "  7|x|7");
     t!(format!("{foo} {bar}", foo=0, bar=1), "0 1");
     t!(format!("{foo} {1} {bar} {0}", 0, 1, foo=2, bar=3), "2 1 3 0");
     t!(format!("{} {0}", "a"), "a a");
     t!(format!("{foo_bar}", foo_bar=1), "1");
+    t!(format!("{name} and {name}", name="x"), "x and x");
+    t!(format!("{n:3d}|{n:d}", n=5), "  5|5");
+    t!(format!("{} {x} {}", 1, 2, x=3), "1 3 2");
 
     // Methods should probably work
     t!(format!("{0, plural, =1{a#} =2{b#} zero{c#} other{d#}}", 0u), "c0");
@@ -116,6 +121,10 @@ pub fn main() {
     t!(format!("{:2.4s}", "aa"), "aa");
     t!(format!("{:2.4s}", "a"), "a ");
     t!(format!("{:0>2s}", "a"), "0a");
+    t!(format!("{:^4s}", "a"), " a  ");
+    t!(format!("{:^5s}", "a"), "  a  ");
+    t!(format!("{:*^6s}", "ab"), "**ab**");
+    t!(format!("{:^2s}", "abc"), "abc");
     t!(format!("{:.*s}", 4, "aaaaaaaaaaaaaaaaaa"), "aaaa");
     t!(format!("{:.1$s}", "aaaaaaaaaaaaaaaaaa", 4), "aaaa");
     t!(format!("{:.a$s}", "aaaaaaaaaaaaaaaaaa", a=4), "aaaa");
@@ -185,6 +194,8 @@ pub fn main() {
     t!(format!("{:>3d}", 1),  "  1");
     t!(format!("{:>+3d}", 1), " +1");
     t!(format!("{:<3d}", 1), "1  ");
+    t!(format!("{:^3d}", 1), " 1 ");
+    t!(format!("{:0^6d}", -1), "00-100");
     t!(format!("{:#d}", 1), "1");
     t!(format!("{:#x}", 10), "0xa");
     t!(format!("{:#X}", 10), "0xA");
@@ -204,6 +215,14 @@ pub fn main() {
     t!(format!("{:#X}", -1u8), "0xFF");
     t!(format!("{:#t}", -1u8), "0b11111111");
     t!(format!("{:#o}", -1u8), "0o377");
+    t!(format!("{:08t}", 5u), "00000101");
+    t!(format!("{:#08t}", 5u), "0b000101");
+    t!(format!("{:5o}", 8u), "   10");
+    t!(format!("{:05o}", 8u), "00010");
+    t!(format!("{:#6o}", 8u), "  0o10");
+    t!(format!("{:04X}", 255u), "00FF");
+    t!(format!("{:#06X}", 255u), "0x00FF");
+    t!(format!("{:<6x}", 255u), "ff    ");
 
     // Signed combinations
     t!(format!("{:+5d}", 1),  "   +1");
@@ -220,6 +239,17 @@ pub fn main() {
     t!(format!("{:10.3f}", 1.0f64),   "     1.000");
     t!(format!("{:+10.3f}", 1.0f64),  "    +1.000");
     t!(format!("{:+10.3f}", -1.0f64), "    -1.000");
+    t!(format!("{:e}", 1234.5f64), "1.2345e+3");
+    t!(format!("{:.2e}", 1234.5f64), "1.23e+3");
+    t!(format!("{:E}", 0.00012f64), "1.2E-4");
+    t!(format!("{:e}", 0.0f64), "0e+0");
+    t!(format!("{:.1e}", 9.96f64), "1.0e+1");
+    t!(format!("{:e}", -1.5f32), "-1.5e+0");
+    t!(format!("{:.3E}", 1.0f32), "1.000E+0");
+    t!(format!("{:10.2e}", 1234.5f64), "   1.23e+3");
+    t!(format!("{:e}", 1e300f64), "1e+300");
+    t!(format!("{:<10.3f}", 1.0f64),  "1.000     ");
+    t!(format!("{:^9.1f}", 1.0f64),   "   1.0   ");
 
     // Escaping
     t!(format!("\\{"), "{");
@@ -228,6 +258,7 @@ pub fn main() {
     t!(format!("\\\\"), "\\");
 
     test_write();
+    test_write_str();
     test_print();
 
     // make sure that format! doesn't move out of local variables
@@ -243,6 +274,7 @@ pub fn main() {
     }
 
     test_format_args();
+    test_pointer();
 
     // test that trailing commas are acceptable
     format!("{}", "test",);
@@ -266,6 +298,30 @@ fn test_write() {
     t!(s, "34helloline\nbar\n");
 }
 
+// Strings can be used as the destination of `write!` through a `fmt::Adapter`
+// as well as through the `fmt::Write` trait directly.
+fn test_write_str() {
+    let mut s = ~"";
+    {
+        let mut a = fmt::Adapter::new(&mut s);
+        write!(&mut a as &mut io::Writer, "{}", 3);
+        let w = &mut a as &mut io::Writer;
+        write!(w, "{foo}", foo=4);
+        writeln!(w, "{:s}", "line");
+
+        // A character split across writes comes out whole.
+        let snowman = "☃".as_bytes();
+        w.write(snowman.slice_to(1));
+        w.write(snowman.slice_from(1));
+        w.write_u8('!' as u8);
+    }
+    {
+        let w = &mut s as &mut fmt::Write;
+        w.write_str("☃");
+    }
+    t!(s, "34line\n☃!☃");
+}
+
 // Just make sure that the macros are defined, there's not really a lot that we
 // can do with them just yet (to test the output)
 fn test_print() {
@@ -274,6 +330,11 @@ fn test_print() {
     println!("hello");
     println!("this is a {}", "test");
     println!("{foo}", foo="bar");
+    eprint!("hi");
+    eprint!("{:?}", ~[0u8]);
+    eprintln!("hello");
+    eprintln!("this is a {}", "test");
+    eprintln!("{foo}", foo="bar");
 }
 
 // Just make sure that the macros are defined, there's not really a lot that we
@@ -292,3 +353,15 @@ fn test_format_args() {
     let s = format_args!(fmt::format, "hello {}", "world");
     t!(s, "hello world");
 }
+
+// References and owned boxes format as the address of what they point to
+fn test_pointer() {
+    let mut a = 3;
+    let addr = format!("{:p}", &a as *int);
+    assert!(addr.starts_with("0x"));
+    t!(format!("{:p}", &a), addr);
+    t!(format!("{:p}", &mut a), addr);
+
+    let b = ~4;
+    t!(format!("{:p}", b), format!("{:p}", &*b as *int));
+}